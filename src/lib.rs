@@ -1,32 +1,64 @@
 #[pyo3::pymodule]
 mod rust_geo_python {
-    use ndarray::parallel::prelude::ParallelIterator;
+    use ndarray::parallel::prelude::{IndexedParallelIterator, ParallelIterator};
     use numpy::ndarray::{Array1, Array2, Axis};
     use numpy::{
         IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2, PyUntypedArrayMethods,
     };
 
+    use geo::buffer::{BufferStyle, LineCap, LineJoin};
+    use geo::concave_hull::ConcaveHullOptions;
+    use geo::line_intersection::{LineIntersection, line_intersection};
+    use geo::line_measures::FrechetDistance;
     use geo::orient::{Direction, Orient};
+    use geo::triangulate_delaunay::DelaunayTriangulationConfig;
     use geo::{
-        Area, BooleanOps, Buffer, Contains, ContainsProperly, Distance, Euclidean,
-        HausdorffDistance, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
-        Simplify, unary_union,
+        AffineOps, AffineTransform, Area, BooleanOps, BoundingRect, Buffer, Centroid,
+        ChaikinSmoothing, Closest, ClosestPoint, ConcaveHull, Contains, ContainsProperly,
+        ConvexHull, Coord, CoordsIter, Densify, Distance, Euclidean, Geodesic, GeodesicArea,
+        Geometry, HausdorffDistance, Haversine, InteriorPoint, InterpolateLine, InterpolatePoint,
+        Intersects, Length, Line, LineLocatePoint, LineString, LineStringSegmentize,
+        LineStringSegmentizeHaversine, LinesIter, MapCoords, MinimumRotatedRect, MultiLineString,
+        MultiPoint, MultiPolygon, Point, Polygon, Rect, RemoveRepeatedPoints, Rhumb, Simplify,
+        SimplifyVw, SimplifyVwPreserve, Triangle, TriangulateDelaunay, TriangulateEarcut,
+        Validation, Winding, unary_union,
     };
     use ndarray::parallel::prelude::IntoParallelIterator;
     use ndarray::{ArrayView1, ArrayView2};
+    use proj4rs::proj::Proj;
+    use pyo3::exceptions::PyValueError;
     use pyo3::{Bound, PyResult, Python};
     use pyo3::{IntoPyObjectExt, prelude::*};
+    use rstar::primitives::GeomWithData;
+    use rstar::{RTree, RTreeObject};
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
+    use wkb::Endianness;
+    use wkb::writer::{WriteOptions, write_geometry};
     use wkt::ToWkt;
 
-    fn point_poly_distance(x: ArrayView1<f64>, y: ArrayView2<f64>) -> f64 {
+    fn point_poly_distance(x: ArrayView1<f64>, y: ArrayView2<f64>, metric: Metric) -> f64 {
         let path = y
             .axis_iter(Axis(0))
             .map(|x| Point::new(x[0], x[1]))
             .collect::<LineString>();
         let point = Point::new(x[0], x[1]);
-        let distance = Euclidean.distance(&point, &path);
-        distance
+        match metric {
+            Metric::Euclidean => Euclidean.distance(&point, &path),
+            Metric::Haversine => point_metric_distance(
+                point,
+                &measured_segments(path.lines(), &Haversine),
+                &Haversine,
+            ),
+            Metric::Geodesic => point_metric_distance(
+                point,
+                &measured_segments(path.lines(), &Geodesic),
+                &Geodesic,
+            ),
+            Metric::Rhumb => {
+                point_metric_distance(point, &measured_segments(path.lines(), &Rhumb), &Rhumb)
+            }
+        }
     }
 
     #[pyfunction(name = "point_polygon_distance")]
@@ -36,75 +68,1567 @@ mod rust_geo_python {
     ) -> PyResult<f64> {
         let x = x.as_array();
         let y = y.as_array();
-        let distance = point_poly_distance(x, y);
+        let distance = point_poly_distance(x, y, Metric::Euclidean);
         Ok(distance)
     }
 
     #[pyfunction(name = "points_polygon_distance")]
+    #[pyo3(signature = (x, y, metric="euclidean"))]
     fn points_poly_distance_py<'py>(
         py: Python<'py>,
         x: PyReadonlyArray2<'py, f64>,
         y: PyReadonlyArray2<'py, f64>,
-    ) -> Bound<'py, PyArray1<f64>> {
+        metric: &str,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let metric = parse_metric(metric)?;
         let x = x.as_array();
         let y = y.as_array();
         let distances = x
             .axis_iter(Axis(0))
-            .map(|p| point_poly_distance(p, y))
+            .map(|p| point_poly_distance(p, y, metric))
             .collect::<Array1<f64>>();
-        distances.into_pyarray(py)
+        Ok(distances.into_pyarray(py))
+    }
+
+    #[pyfunction(name = "polygon_polygon_distance")]
+    fn poly_poly_distance_py<'py>(
+        x: PyReadonlyArray2<'py, f64>,
+        y: PyReadonlyArray2<'py, f64>,
+    ) -> f64 {
+        let path_x = x
+            .as_array()
+            .axis_iter(Axis(0))
+            .map(|x| Point::new(x[0], x[1]))
+            .collect::<LineString>();
+        let path_y = y
+            .as_array()
+            .axis_iter(Axis(0))
+            .map(|x| Point::new(x[0], x[1]))
+            .collect::<LineString>();
+        Euclidean.distance(&path_x, &path_y)
+    }
+
+    #[pyfunction(name = "points_polygon_dist_mut")]
+    fn points_poly_distance_mut_py<'py>(
+        py: Python<'py>,
+        x: PyReadonlyArray2<'py, f64>,
+        y: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray1<f64>> {
+        let x = x.as_array();
+        let y = y.as_array();
+        let distances_vec = x
+            .axis_iter(Axis(0))
+            .into_par_iter()
+            .map(|p| point_poly_distance(p, y, Metric::Euclidean))
+            .collect::<Vec<f64>>();
+        distances_vec.into_pyarray(py)
+    }
+
+    #[derive(Clone, Copy)]
+    enum Metric {
+        Euclidean,
+        Haversine,
+        Geodesic,
+        Rhumb,
+    }
+
+    fn parse_metric(metric: &str) -> PyResult<Metric> {
+        match metric {
+            "euclidean" => Ok(Metric::Euclidean),
+            "haversine" => Ok(Metric::Haversine),
+            "geodesic" => Ok(Metric::Geodesic),
+            "rhumb" => Ok(Metric::Rhumb),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown metric '{metric}', expected one of 'euclidean', 'haversine', 'geodesic' or 'rhumb'"
+            ))),
+        }
+    }
+
+    fn polygon_perimeter(polygon: &Polygon, metric: &impl Distance<f64, Point, Point>) -> f64 {
+        std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .map(|ls| metric.length(ls))
+            .sum()
+    }
+
+    fn shape_length(shape: &Shapes, metric: &impl Distance<f64, Point, Point>) -> f64 {
+        match shape {
+            Shapes::Point(_) => 0.0,
+            Shapes::MultiPoint(_) => 0.0,
+            Shapes::LineString(p) => metric.length(p.as_ref()),
+            Shapes::MultiLineString(p) => metric.length(p.as_ref()),
+            Shapes::Polygon(p) => polygon_perimeter(p, metric),
+            Shapes::MultiPolygon(p) => p.iter().map(|x| polygon_perimeter(x, metric)).sum(),
+        }
+    }
+
+    fn shape_geometry(shape: &Shapes) -> Geometry {
+        match shape {
+            Shapes::Point(p) => Geometry::Point(*p.as_ref()),
+            Shapes::MultiPoint(p) => Geometry::MultiPoint(p.as_ref().clone()),
+            Shapes::LineString(p) => Geometry::LineString(p.as_ref().clone()),
+            Shapes::MultiLineString(p) => Geometry::MultiLineString(p.as_ref().clone()),
+            Shapes::Polygon(p) => Geometry::Polygon(p.as_ref().clone()),
+            Shapes::MultiPolygon(p) => Geometry::MultiPolygon(p.as_ref().clone()),
+        }
+    }
+
+    fn segment_metric_distance<M>(point: Point, segment: Line, metric: &M) -> f64
+    where
+        M: Distance<f64, Point, Point> + InterpolatePoint<f64>,
+    {
+        let (start, end) = (segment.start_point(), segment.end_point());
+        if start == end {
+            return metric.distance(point, start);
+        }
+        // Golden section search for the closest point along the segment as traced by the
+        // metric itself (great circle, geodesic or rhumb line), the distance is unimodal for
+        // segments shorter than half the globe
+        let distance_at =
+            |ratio: f64| metric.distance(point, metric.point_at_ratio_between(start, end, ratio));
+        let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut lo, mut hi) = (0.0, 1.0);
+        let (mut a, mut b) = (hi - inv_phi, lo + inv_phi);
+        let (mut da, mut db) = (distance_at(a), distance_at(b));
+        while hi - lo > 1e-8 {
+            if da < db {
+                (hi, b, db) = (b, a, da);
+                a = hi - inv_phi * (hi - lo);
+                da = distance_at(a);
+            } else {
+                (lo, a, da) = (a, b, db);
+                b = lo + inv_phi * (hi - lo);
+                db = distance_at(b);
+            }
+        }
+        [
+            da,
+            db,
+            metric.distance(point, start),
+            metric.distance(point, end),
+        ]
+        .into_iter()
+        .fold(f64::INFINITY, f64::min)
+    }
+
+    fn measured_segments<M>(segments: impl Iterator<Item = Line>, metric: &M) -> Vec<(Line, f64)>
+    where
+        M: Distance<f64, Point, Point>,
+    {
+        segments
+            .map(|l| (l, metric.distance(l.start_point(), l.end_point())))
+            .collect()
+    }
+
+    fn point_metric_distance<M>(point: Point, segments: &[(Line, f64)], metric: &M) -> f64
+    where
+        M: Distance<f64, Point, Point> + InterpolatePoint<f64>,
+    {
+        // By the triangle inequality no point of a segment of length L lies closer than
+        // (d(p, start) + d(p, end) - L) / 2. Haversine distances scaled by 0.99 stay below the
+        // haversine, geodesic and rhumb ones, so they bound every segment cheaply and the
+        // search only runs on segments that could still beat the closest one so far
+        let mut bounded = segments
+            .iter()
+            .map(|&(segment, length)| {
+                let ends = Haversine.distance(point, segment.start_point())
+                    + Haversine.distance(point, segment.end_point());
+                ((0.99 * ends - length) / 2.0, segment)
+            })
+            .collect::<Vec<(f64, Line)>>();
+        bounded.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut closest = f64::INFINITY;
+        for (bound, segment) in bounded {
+            if bound >= closest {
+                break;
+            }
+            closest = closest.min(segment_metric_distance(point, segment, metric));
+        }
+        closest
+    }
+
+    fn shape_coords_iter(shape: &Shapes) -> Box<dyn Iterator<Item = Coord> + '_> {
+        match shape {
+            Shapes::Point(p) => Box::new(p.coords_iter()),
+            Shapes::MultiPoint(p) => Box::new(p.coords_iter()),
+            Shapes::LineString(p) => Box::new(p.coords_iter()),
+            Shapes::MultiLineString(p) => Box::new(p.coords_iter()),
+            Shapes::Polygon(p) => Box::new(p.coords_iter()),
+            Shapes::MultiPolygon(p) => Box::new(p.coords_iter()),
+        }
+    }
+
+    // Points count as zero length segments so every shape can be measured segment by segment
+    fn shape_segments(shape: &Shapes) -> Box<dyn Iterator<Item = Line> + '_> {
+        match shape {
+            Shapes::Point(p) => Box::new(std::iter::once(Line::new(p.0, p.0))),
+            Shapes::MultiPoint(p) => Box::new(p.iter().map(|p| Line::new(p.0, p.0))),
+            Shapes::LineString(p) => Box::new(p.lines_iter()),
+            Shapes::MultiLineString(p) => Box::new(p.lines_iter()),
+            Shapes::Polygon(p) => Box::new(p.lines_iter()),
+            Shapes::MultiPolygon(p) => Box::new(p.lines_iter()),
+        }
+    }
+
+    fn shape_intersects<T>(lhs: &T, rhs: &Shapes) -> bool
+    where
+        T: Intersects<Point>
+            + Intersects<MultiPoint>
+            + Intersects<LineString>
+            + Intersects<MultiLineString>
+            + Intersects<Polygon>
+            + Intersects<MultiPolygon>,
+    {
+        match rhs {
+            Shapes::Point(p) => lhs.intersects(p.as_ref()),
+            Shapes::MultiPoint(p) => lhs.intersects(p.as_ref()),
+            Shapes::LineString(p) => lhs.intersects(p.as_ref()),
+            Shapes::MultiLineString(p) => lhs.intersects(p.as_ref()),
+            Shapes::Polygon(p) => lhs.intersects(p.as_ref()),
+            Shapes::MultiPolygon(p) => lhs.intersects(p.as_ref()),
+        }
+    }
+
+    fn shapes_intersect(lhs: &Shapes, rhs: &Shapes) -> bool {
+        match lhs {
+            Shapes::Point(p) => shape_intersects(p.as_ref(), rhs),
+            Shapes::MultiPoint(p) => shape_intersects(p.as_ref(), rhs),
+            Shapes::LineString(p) => shape_intersects(p.as_ref(), rhs),
+            Shapes::MultiLineString(p) => shape_intersects(p.as_ref(), rhs),
+            Shapes::Polygon(p) => shape_intersects(p.as_ref(), rhs),
+            Shapes::MultiPolygon(p) => shape_intersects(p.as_ref(), rhs),
+        }
+    }
+
+    fn metric_distance<M>(lhs: &Shapes, rhs: &Shapes, metric: &M) -> f64
+    where
+        M: Distance<f64, Point, Point> + InterpolatePoint<f64>,
+    {
+        if shapes_intersect(lhs, rhs) {
+            return 0.0;
+        }
+        let lhs_segments = measured_segments(shape_segments(lhs), metric);
+        let rhs_segments = measured_segments(shape_segments(rhs), metric);
+        // the closest pair of disjoint shapes always involves a vertex of one of them
+        shape_coords_iter(lhs)
+            .map(|c| point_metric_distance(Point::from(c), &rhs_segments, metric))
+            .chain(
+                shape_coords_iter(rhs)
+                    .map(|c| point_metric_distance(Point::from(c), &lhs_segments, metric)),
+            )
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[derive(FromPyObject)]
+    enum PointLike<'py> {
+        Point(PyRef<'py, RustPoint>),
+        Coord((f64, f64)),
+    }
+
+    fn shape_lines(shape: &Shapes) -> Vec<Line> {
+        match shape {
+            Shapes::Point(_) => vec![],
+            Shapes::MultiPoint(_) => vec![],
+            Shapes::LineString(p) => p.lines_iter().collect(),
+            Shapes::MultiLineString(p) => p.lines_iter().collect(),
+            Shapes::Polygon(p) => p.lines_iter().collect(),
+            Shapes::MultiPolygon(p) => p.lines_iter().collect(),
+        }
+    }
+
+    fn shortest_line(lhs: &Shapes, rhs: &Shapes) -> Option<Line> {
+        let (lhs_geometry, rhs_geometry) = (shape_geometry(lhs), shape_geometry(rhs));
+        let closest = |geometry: &Geometry, c: Coord| match geometry.closest_point(&Point::from(c))
+        {
+            Closest::Intersection(p) | Closest::SinglePoint(p) => Some(p.0),
+            Closest::Indeterminate => None,
+        };
+        // the closest pair of disjoint shapes always involves a vertex of one of them
+        let line = lhs_geometry
+            .coords_iter()
+            .filter_map(|c| closest(&rhs_geometry, c).map(|p| Line::new(c, p)))
+            .chain(
+                rhs_geometry
+                    .coords_iter()
+                    .filter_map(|c| closest(&lhs_geometry, c).map(|p| Line::new(p, c))),
+            )
+            .min_by(|a, b| Euclidean.length(a).total_cmp(&Euclidean.length(b)))?;
+        if line.start == line.end || !lhs_geometry.intersects(&rhs_geometry) {
+            return Some(line);
+        }
+        // intersecting shapes with no vertex inside the other must cross between vertices
+        let rhs_lines = shape_lines(rhs);
+        shape_lines(lhs)
+            .iter()
+            .flat_map(|a| rhs_lines.iter().filter_map(|b| line_intersection(*a, *b)))
+            .map(|x| match x {
+                LineIntersection::SinglePoint { intersection, .. } => intersection,
+                LineIntersection::Collinear { intersection } => intersection.start,
+            })
+            .next()
+            .map(|c| Line::new(c, c))
+            .or(Some(line))
+    }
+
+    fn line_fraction(linestring: &LineString, value: f64, normalized: bool) -> f64 {
+        let fraction = match normalized {
+            true => value,
+            false => match Euclidean.length(linestring) {
+                length if length > 0.0 => value / length,
+                _ => 0.0,
+            },
+        };
+        // negative values are measured back from the end of the line
+        if fraction < 0.0 {
+            fraction + 1.0
+        } else {
+            fraction
+        }
+    }
+
+    fn line_interpolate(linestring: &LineString, value: f64, normalized: bool) -> Option<Point> {
+        let fraction = line_fraction(linestring, value, normalized);
+        Euclidean.point_at_ratio_from_start(linestring, fraction)
+    }
+
+    fn line_substring(linestring: &LineString, start: f64, end: f64) -> LineString {
+        let total = Euclidean.length(linestring);
+        if total == 0.0 {
+            return linestring.clone();
+        }
+        let (lo, hi) = (
+            start.min(end).clamp(0.0, 1.0),
+            start.max(end).clamp(0.0, 1.0),
+        );
+        let mut cumulative = 0.0;
+        let vertices = linestring.lines().filter_map(|line| {
+            cumulative += Euclidean.length(&line);
+            let fraction = cumulative / total;
+            (fraction > lo && fraction < hi).then_some(line.end)
+        });
+        let mut coords = Euclidean
+            .point_at_ratio_from_start(linestring, lo)
+            .map(|p| p.0)
+            .into_iter()
+            .chain(vertices)
+            .chain(
+                Euclidean
+                    .point_at_ratio_from_start(linestring, hi)
+                    .map(|p| p.0),
+            )
+            .collect::<Vec<Coord>>();
+        if start > end {
+            coords.reverse();
+        }
+        LineString::new(coords)
+    }
+
+    fn dtw_distance(
+        lhs: &LineString,
+        rhs: &LineString,
+        metric: &impl Distance<f64, Point, Point>,
+    ) -> f64 {
+        if lhs.0.is_empty() || rhs.0.is_empty() {
+            return f64::NAN;
+        }
+        let mut prev = vec![f64::INFINITY; rhs.0.len() + 1];
+        let mut cur = vec![f64::INFINITY; rhs.0.len() + 1];
+        prev[0] = 0.0;
+        for a in lhs.points() {
+            cur[0] = f64::INFINITY;
+            for (j, b) in rhs.points().enumerate() {
+                cur[j + 1] = metric.distance(a, b) + prev[j].min(prev[j + 1]).min(cur[j]);
+            }
+            std::mem::swap(&mut prev, &mut cur);
+        }
+        prev[rhs.0.len()]
+    }
+
+    fn frechet_distance_metric(lhs: &LineString, rhs: &LineString, metric: Metric) -> f64 {
+        match metric {
+            Metric::Euclidean => Euclidean.frechet_distance(lhs, rhs),
+            Metric::Haversine => Haversine.frechet_distance(lhs, rhs),
+            Metric::Geodesic => Geodesic.frechet_distance(lhs, rhs),
+            Metric::Rhumb => Rhumb.frechet_distance(lhs, rhs),
+        }
+    }
+
+    fn dtw_distance_metric(lhs: &LineString, rhs: &LineString, metric: Metric) -> f64 {
+        match metric {
+            Metric::Euclidean => dtw_distance(lhs, rhs, &Euclidean),
+            Metric::Haversine => dtw_distance(lhs, rhs, &Haversine),
+            Metric::Geodesic => dtw_distance(lhs, rhs, &Geodesic),
+            Metric::Rhumb => dtw_distance(lhs, rhs, &Rhumb),
+        }
+    }
+
+    fn shape_bounding_rect(shape: &Shapes) -> Option<Rect> {
+        match shape {
+            Shapes::Point(p) => Some(p.bounding_rect()),
+            Shapes::MultiPoint(p) => p.bounding_rect(),
+            Shapes::LineString(p) => p.bounding_rect(),
+            Shapes::MultiLineString(p) => p.bounding_rect(),
+            Shapes::Polygon(p) => p.bounding_rect(),
+            Shapes::MultiPolygon(p) => p.bounding_rect(),
+        }
+    }
+
+    fn rect_to_bounds(rect: Option<Rect>) -> (f64, f64, f64, f64) {
+        match rect {
+            Some(r) => (r.min().x, r.min().y, r.max().x, r.max().y),
+            None => (f64::NAN, f64::NAN, f64::NAN, f64::NAN),
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum SimplifyAlgorithm {
+        Rdp,
+        Vw,
+        VwPreserve,
+    }
+
+    fn parse_simplify_algorithm(algorithm: &str) -> PyResult<SimplifyAlgorithm> {
+        match algorithm {
+            "rdp" => Ok(SimplifyAlgorithm::Rdp),
+            "vw" => Ok(SimplifyAlgorithm::Vw),
+            "vw_preserve" => Ok(SimplifyAlgorithm::VwPreserve),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown simplify algorithm '{algorithm}', expected 'rdp', 'vw' or 'vw_preserve'"
+            ))),
+        }
+    }
+
+    fn simplify_geometry<G>(geometry: &G, epsilon: f64, algorithm: SimplifyAlgorithm) -> G
+    where
+        G: Simplify<f64> + SimplifyVw<f64> + SimplifyVwPreserve<f64>,
+    {
+        match algorithm {
+            SimplifyAlgorithm::Rdp => geometry.simplify(epsilon),
+            SimplifyAlgorithm::Vw => geometry.simplify_vw(epsilon),
+            SimplifyAlgorithm::VwPreserve => geometry.simplify_vw_preserve(epsilon),
+        }
+    }
+
+    fn densify_shape<M>(shape: &Shapes, metric: &M, max_segment_length: f64) -> Shapes
+    where
+        M: Distance<f64, Point, Point> + InterpolatePoint<f64>,
+    {
+        match shape {
+            Shapes::Point(_) => shape.clone(),
+            Shapes::MultiPoint(_) => shape.clone(),
+            Shapes::LineString(p) => {
+                Shapes::LineString(Arc::new(metric.densify(p.as_ref(), max_segment_length)))
+            }
+            Shapes::MultiLineString(p) => {
+                Shapes::MultiLineString(Arc::new(metric.densify(p.as_ref(), max_segment_length)))
+            }
+            Shapes::Polygon(p) => {
+                Shapes::Polygon(Arc::new(metric.densify(p.as_ref(), max_segment_length)))
+            }
+            Shapes::MultiPolygon(p) => {
+                Shapes::MultiPolygon(Arc::new(metric.densify(p.as_ref(), max_segment_length)))
+            }
+        }
+    }
+
+    fn shape_linework(shape: &Shapes) -> PyResult<Vec<&LineString>> {
+        match shape {
+            Shapes::Point(_) | Shapes::MultiPoint(_) => Err(PyValueError::new_err(
+                "Expected a linestring or polygon shape, got points",
+            )),
+            Shapes::LineString(p) => Ok(vec![p.as_ref()]),
+            Shapes::MultiLineString(p) => Ok(p.iter().collect()),
+            Shapes::Polygon(p) => Ok(std::iter::once(p.exterior()).chain(p.interiors()).collect()),
+            Shapes::MultiPolygon(p) => Ok(p
+                .iter()
+                .flat_map(|x| std::iter::once(x.exterior()).chain(x.interiors()))
+                .collect()),
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum CapStyle {
+        Round,
+        Flat,
+        Square,
+    }
+
+    #[derive(Clone, Copy)]
+    enum JoinStyle {
+        Round,
+        Mitre,
+        Bevel,
+    }
+
+    #[derive(Clone, Copy)]
+    struct BufferOptions {
+        cap_style: CapStyle,
+        join_style: JoinStyle,
+        mitre_limit: f64,
+        round_angle: f64,
+        single_sided: bool,
+    }
+
+    impl BufferOptions {
+        fn new(
+            cap_style: &str,
+            join_style: &str,
+            mitre_limit: f64,
+            quad_segs: Option<u32>,
+            single_sided: bool,
+        ) -> PyResult<Self> {
+            let cap_style = match cap_style {
+                "round" => CapStyle::Round,
+                "flat" => CapStyle::Flat,
+                "square" => CapStyle::Square,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "Unknown cap_style '{cap_style}', expected 'round', 'flat' or 'square'"
+                    )));
+                }
+            };
+            let join_style = match join_style {
+                "round" => JoinStyle::Round,
+                "mitre" => JoinStyle::Mitre,
+                "bevel" => JoinStyle::Bevel,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "Unknown join_style '{join_style}', expected 'round', 'mitre' or 'bevel'"
+                    )));
+                }
+            };
+            if mitre_limit <= 0.0 {
+                return Err(PyValueError::new_err("mitre_limit must be positive"));
+            }
+            // Round joins and caps are approximated by chords of length `round_angle * radius`,
+            // 0.2 matches geo's default of roughly eight segments per quarter circle
+            let round_angle = match quad_segs {
+                Some(0) => return Err(PyValueError::new_err("quad_segs must be at least 1")),
+                Some(n) => std::f64::consts::FRAC_PI_2 / n as f64,
+                None => 0.2,
+            };
+            Ok(BufferOptions {
+                cap_style,
+                join_style,
+                mitre_limit,
+                round_angle,
+                single_sided,
+            })
+        }
+
+        fn style(&self, distance: f64) -> BufferStyle<f64> {
+            let line_cap = match self.cap_style {
+                CapStyle::Round => LineCap::Round(self.round_angle),
+                CapStyle::Flat => LineCap::Butt,
+                CapStyle::Square => LineCap::Square,
+            };
+            // i_overlay limits mitres by the sharpest corner angle rather than by the ratio of
+            // mitre length to buffer distance, the two are related by ratio = 1 / sin(angle / 2)
+            let line_join = match self.join_style {
+                JoinStyle::Round => LineJoin::Round(self.round_angle),
+                JoinStyle::Mitre => LineJoin::Miter(2.0 * (1.0 / self.mitre_limit).min(1.0).asin()),
+                JoinStyle::Bevel => LineJoin::Bevel,
+            };
+            BufferStyle::new(distance)
+                .line_cap(line_cap)
+                .line_join(line_join)
+        }
+    }
+
+    fn single_sided_join(
+        vertex: Coord,
+        n1: Coord,
+        n2: Coord,
+        distance: f64,
+        options: &BufferOptions,
+    ) -> Vec<Coord> {
+        let m1 = n1 * distance;
+        let m2 = n2 * distance;
+        let cos_theta = n1.x * n2.x + n1.y * n2.y;
+        let mut ring = vec![vertex, vertex + m1];
+        match options.join_style {
+            JoinStyle::Round => {
+                let a1 = m1.y.atan2(m1.x);
+                let mut theta = m2.y.atan2(m2.x) - a1;
+                if theta > std::f64::consts::PI {
+                    theta -= 2.0 * std::f64::consts::PI;
+                } else if theta < -std::f64::consts::PI {
+                    theta += 2.0 * std::f64::consts::PI;
+                }
+                let steps = (theta.abs() / options.round_angle).ceil().max(1.0) as usize;
+                ring.extend((1..steps).map(|k| {
+                    let a = a1 + theta * k as f64 / steps as f64;
+                    vertex
+                        + Coord {
+                            x: distance.abs() * a.cos(),
+                            y: distance.abs() * a.sin(),
+                        }
+                }));
+            }
+            JoinStyle::Mitre => {
+                let ratio = ((1.0 + cos_theta) / 2.0).sqrt().recip();
+                if ratio <= options.mitre_limit {
+                    ring.push(vertex + (m1 + m2) / (1.0 + cos_theta));
+                }
+            }
+            JoinStyle::Bevel => {}
+        }
+        ring.push(vertex + m2);
+        ring
+    }
+
+    fn single_sided_buffer(
+        ls: &LineString,
+        distance: f64,
+        options: &BufferOptions,
+    ) -> MultiPolygon {
+        let mut coords = ls.0.clone();
+        coords.dedup();
+        let normals = coords
+            .windows(2)
+            .map(|w| {
+                let d = w[1] - w[0];
+                let len = d.x.hypot(d.y);
+                Coord {
+                    x: -d.y / len,
+                    y: d.x / len,
+                }
+            })
+            .collect::<Vec<Coord>>();
+        let mut pieces = coords
+            .windows(2)
+            .zip(&normals)
+            .map(|(w, &n)| {
+                let offset = n * distance;
+                vec![w[0], w[1], w[1] + offset, w[0] + offset]
+            })
+            .collect::<Vec<Vec<Coord>>>();
+        let mut joins = (1..normals.len())
+            .map(|i| (coords[i], normals[i - 1], normals[i]))
+            .collect::<Vec<(Coord, Coord, Coord)>>();
+        if ls.is_closed() && normals.len() > 1 {
+            joins.push((coords[0], normals[normals.len() - 1], normals[0]));
+        }
+        // Only the outside of a turn needs filling, the inside is covered by the segment offsets
+        pieces.extend(
+            joins
+                .into_iter()
+                .filter(|(_, n1, n2)| (n1.x * n2.y - n1.y * n2.x) * distance < 0.0)
+                .map(|(vertex, n1, n2)| single_sided_join(vertex, n1, n2, distance, options)),
+        );
+        let polygons = pieces
+            .into_iter()
+            .map(|ring| Polygon::new(LineString::new(ring), vec![]).orient(Direction::Default))
+            .collect::<Vec<Polygon>>();
+        unary_union(&polygons)
+    }
+
+    fn buffer_shape(shape: &Shapes, distance: f64, options: &BufferOptions) -> MultiPolygon {
+        match shape {
+            Shapes::LineString(p) if options.single_sided => {
+                single_sided_buffer(p, distance, options)
+            }
+            Shapes::MultiLineString(p) if options.single_sided => {
+                let polygons = p
+                    .iter()
+                    .flat_map(|ls| single_sided_buffer(ls, distance, options))
+                    .collect::<Vec<Polygon>>();
+                unary_union(&polygons)
+            }
+            Shapes::Point(p) => p.buffer_with_style(options.style(distance)),
+            Shapes::MultiPoint(p) => p.buffer_with_style(options.style(distance)),
+            Shapes::LineString(p) => p.buffer_with_style(options.style(distance)),
+            Shapes::MultiLineString(p) => p.buffer_with_style(options.style(distance)),
+            Shapes::MultiPolygon(p) => p.buffer_with_style(options.style(distance)),
+            Shapes::Polygon(p) => p.buffer_with_style(options.style(distance)),
+        }
+    }
+
+    #[derive(FromPyObject)]
+    enum Origin {
+        Named(String),
+        Coord((f64, f64)),
+    }
+
+    fn origin_coord(shape: &Shapes, origin: Option<Origin>) -> PyResult<Coord> {
+        let named = match origin {
+            Some(Origin::Coord((x, y))) => return Ok(Coord { x, y }),
+            Some(Origin::Named(name)) => name,
+            None => "center".to_string(),
+        };
+        let coord = match named.as_str() {
+            "center" => shape_bounding_rect(shape).map(|r| r.center()),
+            "centroid" => match shape {
+                Shapes::Point(p) => Some(p.centroid()),
+                Shapes::MultiPoint(p) => p.centroid(),
+                Shapes::LineString(p) => p.centroid(),
+                Shapes::MultiLineString(p) => p.centroid(),
+                Shapes::MultiPolygon(p) => p.centroid(),
+                Shapes::Polygon(p) => p.centroid(),
+            }
+            .map(|p| p.0),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown origin '{named}', expected 'center', 'centroid' or an (x, y) tuple"
+                )));
+            }
+        };
+        // Empty shapes are unchanged by any transform so the origin is irrelevant
+        Ok(coord.unwrap_or(Coord { x: 0.0, y: 0.0 }))
+    }
+
+    fn transform_shape(shape: &Shapes, transform: &AffineTransform) -> Shapes {
+        match shape {
+            Shapes::Point(p) => Shapes::Point(Arc::new(p.affine_transform(transform))),
+            Shapes::MultiPoint(p) => Shapes::MultiPoint(Arc::new(p.affine_transform(transform))),
+            Shapes::LineString(p) => Shapes::LineString(Arc::new(p.affine_transform(transform))),
+            Shapes::MultiLineString(p) => {
+                Shapes::MultiLineString(Arc::new(p.affine_transform(transform)))
+            }
+            Shapes::Polygon(p) => Shapes::Polygon(Arc::new(p.affine_transform(transform))),
+            Shapes::MultiPolygon(p) => {
+                Shapes::MultiPolygon(Arc::new(p.affine_transform(transform)))
+            }
+        }
+    }
+
+    fn shape_coords(shape: &Shapes) -> Vec<Coord> {
+        match shape {
+            Shapes::Point(p) => p.coords_iter().collect(),
+            Shapes::MultiPoint(p) => p.coords_iter().collect(),
+            Shapes::LineString(p) => p.coords_iter().collect(),
+            Shapes::MultiLineString(p) => p.coords_iter().collect(),
+            Shapes::Polygon(p) => p.coords_iter().collect(),
+            Shapes::MultiPolygon(p) => p.coords_iter().collect(),
+        }
+    }
+
+    fn rebuild_linestring(ls: &LineString, coords: &mut impl Iterator<Item = Coord>) -> LineString {
+        ls.0.iter().zip(coords).map(|(_, c)| c).collect()
+    }
+
+    fn rebuild_polygon(polygon: &Polygon, coords: &mut impl Iterator<Item = Coord>) -> Polygon {
+        let exterior = rebuild_linestring(polygon.exterior(), coords);
+        let interiors = polygon
+            .interiors()
+            .iter()
+            .map(|ls| rebuild_linestring(ls, coords))
+            .collect::<Vec<LineString>>();
+        Polygon::new(exterior, interiors)
+    }
+
+    fn rebuild_shape(shape: &Shapes, coords: Vec<Coord>) -> Shapes {
+        let mut coords = coords.into_iter();
+        match shape {
+            Shapes::Point(_) => Shapes::Point(Arc::new(Point::from(coords.next().unwrap()))),
+            Shapes::MultiPoint(p) => Shapes::MultiPoint(Arc::new(
+                p.iter().zip(coords).map(|(_, c)| Point::from(c)).collect(),
+            )),
+            Shapes::LineString(p) => {
+                Shapes::LineString(Arc::new(rebuild_linestring(p, &mut coords)))
+            }
+            Shapes::MultiLineString(p) => Shapes::MultiLineString(Arc::new(
+                p.iter()
+                    .map(|ls| rebuild_linestring(ls, &mut coords))
+                    .collect(),
+            )),
+            Shapes::Polygon(p) => Shapes::Polygon(Arc::new(rebuild_polygon(p, &mut coords))),
+            Shapes::MultiPolygon(p) => Shapes::MultiPolygon(Arc::new(
+                p.iter().map(|x| rebuild_polygon(x, &mut coords)).collect(),
+            )),
+        }
+    }
+
+    #[derive(FromPyObject)]
+    enum Crs {
+        Epsg(u32),
+        Name(String),
+    }
+
+    fn epsg_proj_string(code: u32) -> Option<String> {
+        match code {
+            4326 => Some("+proj=longlat +datum=WGS84 +no_defs".to_string()),
+            3857 => Some(
+                "+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1 +units=m +no_defs"
+                    .to_string(),
+            ),
+            32601..=32660 => Some(format!(
+                "+proj=utm +zone={} +datum=WGS84 +units=m +no_defs",
+                code - 32600
+            )),
+            32701..=32760 => Some(format!(
+                "+proj=utm +zone={} +south +datum=WGS84 +units=m +no_defs",
+                code - 32700
+            )),
+            _ => None,
+        }
+    }
+
+    fn parse_crs(crs: &Crs) -> PyResult<Proj> {
+        let proj_string = match crs {
+            Crs::Epsg(code) => epsg_proj_string(*code),
+            Crs::Name(name) if name.trim_start().starts_with('+') => Some(name.clone()),
+            Crs::Name(name) => match name.split_once(':') {
+                Some((authority, code)) if authority.eq_ignore_ascii_case("EPSG") => {
+                    code.trim().parse::<u32>().ok().and_then(epsg_proj_string)
+                }
+                _ if name.eq_ignore_ascii_case("WGS84") => epsg_proj_string(4326),
+                _ => None,
+            },
+        };
+        let proj_string = proj_string.ok_or_else(|| {
+            let given = match crs {
+                Crs::Epsg(code) => format!("EPSG:{code}"),
+                Crs::Name(name) => format!("'{name}'"),
+            };
+            PyValueError::new_err(format!(
+                "Unsupported CRS {given}, expected EPSG:4326, EPSG:3857, a WGS84 UTM zone (EPSG:326xx or EPSG:327xx) or a proj string"
+            ))
+        })?;
+        Proj::from_proj_string(&proj_string)
+            .map_err(|e| PyValueError::new_err(format!("Invalid CRS '{proj_string}': {e}")))
+    }
+
+    fn proj_string_srid(proj_string: &str) -> Option<i32> {
+        let tokens = |s: &str| {
+            let mut tokens = s
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<String>>();
+            tokens.sort();
+            tokens
+        };
+        let target = tokens(proj_string);
+        [4326, 3857]
+            .into_iter()
+            .chain(32601..=32660)
+            .chain(32701..=32760)
+            .find(|&code| epsg_proj_string(code).is_some_and(|s| tokens(&s) == target))
+            .and_then(|code| i32::try_from(code).ok())
+    }
+
+    fn crs_srid(crs: &Crs) -> Option<i32> {
+        match crs {
+            Crs::Epsg(code) => i32::try_from(*code).ok(),
+            Crs::Name(name) if name.trim_start().starts_with('+') => proj_string_srid(name),
+            Crs::Name(name) => match name.split_once(':') {
+                Some((authority, code)) if authority.eq_ignore_ascii_case("EPSG") => {
+                    code.trim().parse::<i32>().ok()
+                }
+                _ if name.eq_ignore_ascii_case("WGS84") => Some(4326),
+                _ => None,
+            },
+        }
+    }
+
+    fn shape_to_ewkb(inner: &Shapes, srid: Option<i32>) -> PyResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        let options = WriteOptions {
+            endianness: Endianness::LittleEndian,
+        };
+        match inner {
+            Shapes::Point(p) => write_geometry(&mut buf, p.as_ref(), &options),
+            Shapes::MultiPoint(p) => write_geometry(&mut buf, p.as_ref(), &options),
+            Shapes::LineString(p) => write_geometry(&mut buf, p.as_ref(), &options),
+            Shapes::MultiLineString(p) => write_geometry(&mut buf, p.as_ref(), &options),
+            Shapes::Polygon(p) => write_geometry(&mut buf, p.as_ref(), &options),
+            Shapes::MultiPolygon(p) => write_geometry(&mut buf, p.as_ref(), &options),
+        }
+        .map_err(|e| PyValueError::new_err(format!("Failed to write WKB: {e}")))?;
+        if let Some(srid) = srid {
+            // EWKB flags the geometry type and stores the SRID right after it
+            let geometry_type = u32::from_le_bytes([buf[1], buf[2], buf[3], buf[4]]) | 0x2000_0000;
+            buf.splice(
+                1..5,
+                geometry_type
+                    .to_le_bytes()
+                    .into_iter()
+                    .chain(srid.to_le_bytes()),
+            );
+        }
+        Ok(buf)
+    }
+
+    fn reproject_coords(coords: &mut [Coord], src: &Proj, dst: &Proj) -> PyResult<()> {
+        // proj4rs works in radians for geographic coordinate systems
+        coords.iter_mut().try_for_each(|c| {
+            let (x, y) = if src.is_latlong() {
+                (c.x.to_radians(), c.y.to_radians())
+            } else {
+                (c.x, c.y)
+            };
+            let (x, y) = proj4rs::adaptors::transform_xy(src, dst, x, y)
+                .map_err(|e| PyValueError::new_err(format!("Reprojection failed: {e}")))?;
+            *c = if dst.is_latlong() {
+                Coord {
+                    x: x.to_degrees(),
+                    y: y.to_degrees(),
+                }
+            } else {
+                Coord { x, y }
+            };
+            Ok(())
+        })
+    }
+
+    type CoordKey = (u64, u64);
+
+    fn coord_key(c: Coord) -> CoordKey {
+        // Adding 0.0 folds -0.0 into 0.0 so both hash to the same key
+        ((c.x + 0.0).to_bits(), (c.y + 0.0).to_bits())
+    }
+
+    fn clip_half_plane(cell: &[Coord], site: Coord, other: Coord) -> Vec<Coord> {
+        // keep the side of the perpendicular bisector between site and other that holds site
+        let normal = other - site;
+        let midpoint = (site + other) / 2.0;
+        let side = |c: Coord| (c.x - midpoint.x) * normal.x + (c.y - midpoint.y) * normal.y;
+        let mut clipped = Vec::with_capacity(cell.len() + 1);
+        for (i, current) in cell.iter().enumerate() {
+            let next = cell[(i + 1) % cell.len()];
+            let (d_current, d_next) = (side(*current), side(next));
+            if d_current <= 0.0 {
+                clipped.push(*current);
+            }
+            if (d_current < 0.0 && d_next > 0.0) || (d_current > 0.0 && d_next < 0.0) {
+                clipped.push(*current + (next - *current) * (d_current / (d_current - d_next)));
+            }
+        }
+        clipped
+    }
+
+    fn voronoi_cells(sites: &[Coord], frame: Rect) -> PyResult<Vec<Polygon>> {
+        let mut lookup: HashMap<CoordKey, usize> = HashMap::new();
+        let unique = sites
+            .iter()
+            .filter(|c| {
+                let next = lookup.len();
+                *lookup.entry(coord_key(**c)).or_insert(next) == next
+            })
+            .copied()
+            .collect::<Vec<Coord>>();
+        let triangles = LineString::new(unique.clone())
+            .unconstrained_triangulation()
+            .map_err(|e| PyValueError::new_err(format!("Triangulation failed: {e}")))?;
+        // Delaunay neighbours are enough to bound each cell; collinear sites have no
+        // triangles, so every other site is treated as a neighbour instead
+        let mut neighbours: Vec<HashSet<usize>> = vec![HashSet::new(); unique.len()];
+        match triangles.is_empty() {
+            true => (0..unique.len()).for_each(|i| {
+                neighbours[i] = (0..unique.len()).filter(|j| *j != i).collect();
+            }),
+            false => triangles.iter().for_each(|t| {
+                let [a, b, c] = t.to_array().map(|x| lookup[&coord_key(x)]);
+                for (i, j) in [(a, b), (b, c), (c, a)] {
+                    neighbours[i].insert(j);
+                    neighbours[j].insert(i);
+                }
+            }),
+        }
+        let frame = frame.to_polygon().exterior().0[..4].to_vec();
+        let cells = unique
+            .iter()
+            .zip(&neighbours)
+            .map(|(site, others)| {
+                let cell = others.iter().fold(frame.clone(), |cell, j| {
+                    clip_half_plane(&cell, *site, unique[*j])
+                });
+                Polygon::new(LineString::new(cell), vec![])
+            })
+            .collect::<Vec<Polygon>>();
+        Ok(sites
+            .iter()
+            .map(|c| cells[lookup[&coord_key(*c)]].clone())
+            .collect())
+    }
+
+    fn clip_cells(cells: &[Polygon], extent: &MultiPolygon) -> Vec<MultiPolygon> {
+        cells.iter().map(|cell| cell.intersection(extent)).collect()
+    }
+
+    fn node_lines(lines: &[&LineString]) -> Vec<(Coord, Coord)> {
+        let segments = lines
+            .iter()
+            .flat_map(|ls| ls.lines())
+            .filter(|l| l.start != l.end)
+            .collect::<Vec<Line>>();
+        let mut splits = segments
+            .iter()
+            .map(|l| vec![l.start, l.end])
+            .collect::<Vec<Vec<Coord>>>();
+        // Only segments whose envelopes overlap can intersect
+        let index = RTree::bulk_load(
+            segments
+                .iter()
+                .enumerate()
+                .map(|(i, l)| GeomWithData::new(*l, i))
+                .collect::<Vec<GeomWithData<Line, usize>>>(),
+        );
+        for i in 0..segments.len() {
+            let mut candidates = index
+                .locate_in_envelope_intersecting(&segments[i].envelope())
+                .map(|x| x.data)
+                .filter(|&j| j > i)
+                .collect::<Vec<usize>>();
+            candidates.sort_unstable();
+            for j in candidates {
+                let points = match line_intersection(segments[i], segments[j]) {
+                    Some(LineIntersection::SinglePoint { intersection, .. }) => vec![intersection],
+                    Some(LineIntersection::Collinear { intersection }) => {
+                        vec![intersection.start, intersection.end]
+                    }
+                    None => continue,
+                };
+                for point in points {
+                    // three or more segments crossing at one spot give intersections a few ulps
+                    // apart, reuse a split point already on either segment so they key alike
+                    let point = [i, j]
+                        .iter()
+                        .find_map(|&k| {
+                            let delta = segments[k].delta();
+                            let tolerance = 1e-9 * delta.x.hypot(delta.y);
+                            splits[k]
+                                .iter()
+                                .find(|c| (c.x - point.x).hypot(c.y - point.y) <= tolerance)
+                                .copied()
+                        })
+                        .unwrap_or(point);
+                    splits[i].push(point);
+                    splits[j].push(point);
+                }
+            }
+        }
+        let mut seen: HashSet<(CoordKey, CoordKey)> = HashSet::new();
+        let mut edges = vec![];
+        for (segment, mut points) in segments.iter().zip(splits) {
+            let delta = segment.delta();
+            let along =
+                |c: &Coord| (c.x - segment.start.x) * delta.x + (c.y - segment.start.y) * delta.y;
+            points.sort_by(|a, b| along(a).total_cmp(&along(b)));
+            points.dedup_by_key(|c| coord_key(*c));
+            for pair in points.windows(2) {
+                let (a, b) = (coord_key(pair[0]), coord_key(pair[1]));
+                if seen.insert((a.min(b), a.max(b))) {
+                    edges.push((pair[0], pair[1]));
+                }
+            }
+        }
+        edges
+    }
+
+    struct PlanarGraph {
+        nodes: Vec<Coord>,
+        edges: Vec<(usize, usize)>,
+        alive: Vec<bool>,
+    }
+
+    impl PlanarGraph {
+        fn new(segments: &[(Coord, Coord)]) -> Self {
+            let mut lookup: HashMap<CoordKey, usize> = HashMap::new();
+            let mut nodes = vec![];
+            let mut node = |c: Coord| {
+                *lookup.entry(coord_key(c)).or_insert_with(|| {
+                    nodes.push(c);
+                    nodes.len() - 1
+                })
+            };
+            let edges = segments
+                .iter()
+                .map(|(a, b)| (node(*a), node(*b)))
+                .collect::<Vec<(usize, usize)>>();
+            let alive = vec![true; edges.len()];
+            PlanarGraph {
+                nodes,
+                edges,
+                alive,
+            }
+        }
+
+        fn edge_line(&self, e: usize) -> LineString {
+            let (a, b) = self.edges[e];
+            LineString::new(vec![self.nodes[a], self.nodes[b]])
+        }
+
+        fn remove_dangles(&mut self) -> Vec<usize> {
+            let mut incident: Vec<Vec<usize>> = vec![vec![]; self.nodes.len()];
+            self.edges.iter().enumerate().for_each(|(e, (a, b))| {
+                if self.alive[e] {
+                    incident[*a].push(e);
+                    incident[*b].push(e);
+                }
+            });
+            let mut degree = incident.iter().map(|x| x.len()).collect::<Vec<usize>>();
+            let mut queue = (0..self.nodes.len())
+                .filter(|n| degree[*n] == 1)
+                .collect::<Vec<usize>>();
+            let mut dangles = vec![];
+            while let Some(n) = queue.pop() {
+                let Some(e) = incident[n].iter().copied().find(|e| self.alive[*e]) else {
+                    continue;
+                };
+                self.alive[e] = false;
+                dangles.push(e);
+                let (a, b) = self.edges[e];
+                for end in [a, b] {
+                    degree[end] -= 1;
+                    if degree[end] == 1 {
+                        queue.push(end);
+                    }
+                }
+            }
+            dangles
+        }
+
+        // Half-edge 2e runs along edge e and 2e + 1 runs back; every cycle keeps its face on
+        // the left, so bounded faces come out counter-clockwise.
+        fn cycles(&self) -> Vec<Vec<usize>> {
+            let origin = |h: usize| match h % 2 {
+                0 => self.edges[h / 2].0,
+                _ => self.edges[h / 2].1,
+            };
+            let mut outgoing: Vec<Vec<usize>> = vec![vec![]; self.nodes.len()];
+            (0..self.edges.len() * 2)
+                .filter(|h| self.alive[h / 2])
+                .for_each(|h| outgoing[origin(h)].push(h));
+            let angle = |h: usize| {
+                let (a, b) = (self.nodes[origin(h)], self.nodes[origin(h ^ 1)]);
+                (b.y - a.y).atan2(b.x - a.x)
+            };
+            let mut position = vec![0; self.edges.len() * 2];
+            outgoing.iter_mut().for_each(|hs| {
+                hs.sort_by(|a, b| angle(*a).total_cmp(&angle(*b)));
+                hs.iter().enumerate().for_each(|(i, h)| position[*h] = i);
+            });
+            let next = |h: usize| {
+                let twin = h ^ 1;
+                let around = &outgoing[origin(twin)];
+                around[(position[twin] + around.len() - 1) % around.len()]
+            };
+            let mut visited = vec![false; self.edges.len() * 2];
+            let mut cycles = vec![];
+            for start in (0..self.edges.len() * 2).filter(|h| self.alive[h / 2]) {
+                if visited[start] {
+                    continue;
+                }
+                let mut cycle = vec![];
+                let mut h = start;
+                while !visited[h] {
+                    visited[h] = true;
+                    cycle.push(h);
+                    h = next(h);
+                }
+                cycles.push(cycle);
+            }
+            cycles
+        }
+
+        fn cycle_ring(&self, cycle: &[usize]) -> LineString {
+            cycle
+                .iter()
+                .map(|h| match h % 2 {
+                    0 => self.nodes[self.edges[h / 2].0],
+                    _ => self.nodes[self.edges[h / 2].1],
+                })
+                .collect()
+        }
+    }
+
+    fn polygonize_lines(lines: &[&LineString]) -> (MultiPolygon, MultiLineString, MultiLineString) {
+        let mut graph = PlanarGraph::new(&node_lines(lines));
+        let mut dangles = vec![];
+        let mut cut_edges = vec![];
+        let cycles = loop {
+            dangles.extend(graph.remove_dangles());
+            let cycles = graph.cycles();
+            // an edge with the same face on both sides does not bound any polygon
+            let cut = cycles
+                .iter()
+                .flat_map(|cycle| {
+                    let halves = cycle.iter().copied().collect::<HashSet<usize>>();
+                    cycle
+                        .iter()
+                        .filter(|h| *h % 2 == 0 && halves.contains(&(*h + 1)))
+                        .map(|h| h / 2)
+                        .collect::<Vec<usize>>()
+                })
+                .collect::<Vec<usize>>();
+            if cut.is_empty() {
+                break cycles;
+            }
+            cut.iter().for_each(|e| graph.alive[*e] = false);
+            cut_edges.extend(cut);
+        };
+        let rings = cycles
+            .iter()
+            .map(|cycle| {
+                let ring = Polygon::new(graph.cycle_ring(cycle), vec![]);
+                let area = ring.signed_area();
+                (ring, area)
+            })
+            .collect::<Vec<(Polygon, f64)>>();
+        let shells = rings
+            .iter()
+            .filter(|(_, area)| *area > 0.0)
+            .collect::<Vec<&(Polygon, f64)>>();
+        let mut holes: Vec<Vec<LineString>> = vec![vec![]; shells.len()];
+        // the clockwise cycles outline each connected piece of linework, which is a hole in
+        // the smallest face of another piece that surrounds it
+        rings
+            .iter()
+            .filter(|(_, area)| *area < 0.0)
+            .for_each(|(ring, area)| {
+                let inside = Point::from(ring.exterior().0[0]);
+                let enclosing = shells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (shell, shell_area))| {
+                        *shell_area > -area && shell.contains(&inside)
+                    })
+                    .min_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b));
+                if let Some((i, _)) = enclosing {
+                    holes[i].push(ring.exterior().clone());
+                }
+            });
+        let polygons = shells
+            .iter()
+            .zip(holes)
+            .map(|((shell, _), holes)| Polygon::new(shell.exterior().clone(), holes))
+            .collect::<MultiPolygon>();
+        let dangles = dangles.iter().map(|e| graph.edge_line(*e)).collect();
+        let cut_edges = cut_edges.iter().map(|e| graph.edge_line(*e)).collect();
+        (polygons, dangles, cut_edges)
+    }
+
+    #[derive(FromPyObject)]
+    enum Linework {
+        Shape(Shape),
+        Shapes(Vec<Shape>),
+    }
+
+    fn linework_polygonize(
+        lines: Linework,
+    ) -> PyResult<(MultiPolygon, MultiLineString, MultiLineString, Option<i32>)> {
+        let shapes = match lines {
+            Linework::Shape(shape) => vec![shape],
+            Linework::Shapes(shapes) => shapes,
+        };
+        let srid = shapes
+            .iter()
+            .try_fold(None, |srid, x| common_srid(srid, x.srid))?;
+        let lines = shapes
+            .iter()
+            .map(|shape| shape_linework(&shape.inner))
+            .collect::<PyResult<Vec<Vec<&LineString>>>>()?
+            .concat();
+        let (polygons, dangles, cut_edges) = polygonize_lines(&lines);
+        Ok((polygons, dangles, cut_edges, srid))
+    }
+
+    type EarcutArrays<'py> = (Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<u32>>);
+
+    fn earcut_buffers(polygons: &[Polygon]) -> PyResult<(Vec<f64>, Vec<u32>)> {
+        let mut vertices: Vec<f64> = vec![];
+        let mut indices: Vec<u32> = vec![];
+        for polygon in polygons {
+            let raw = polygon.earcut_triangles_raw();
+            // earcut sees every ring closed, the closing coordinate is dropped from the vertex
+            // buffer and any index pointing at it is sent back to the ring's first vertex
+            let mut remap: Vec<usize> = Vec::with_capacity(raw.vertices.len() / 2);
+            for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                let start = vertices.len() / 2;
+                let closed = ring.0.len() > 1 && ring.is_closed();
+                for (i, c) in ring.0.iter().enumerate() {
+                    if closed && i == ring.0.len() - 1 {
+                        remap.push(start);
+                    } else {
+                        remap.push(vertices.len() / 2);
+                        vertices.extend([c.x, c.y]);
+                    }
+                }
+            }
+            indices.extend(
+                raw.triangle_indices
+                    .iter()
+                    .map(|&i| u32::try_from(remap[i]))
+                    .collect::<Result<Vec<u32>, _>>()
+                    .map_err(|_| {
+                        PyValueError::new_err("Too many vertices for a uint32 index buffer")
+                    })?,
+            );
+        }
+        Ok((vertices, indices))
+    }
+
+    fn earcut_arrays<'py>(py: Python<'py>, polygons: &[Polygon]) -> PyResult<EarcutArrays<'py>> {
+        let (vertices, indices) = py.detach(|| earcut_buffers(polygons))?;
+        let vertices = Array2::from_shape_vec((vertices.len() / 2, 2), vertices)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let indices = Array2::from_shape_vec((indices.len() / 3, 3), indices)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok((
+            PyArray2::from_owned_array(py, vertices),
+            PyArray2::from_owned_array(py, indices),
+        ))
+    }
+
+    #[derive(FromPyObject)]
+    enum ShapeOrPoints<'py> {
+        Shape(Shape),
+        Points(PyReadonlyArray2<'py, f64>),
+    }
+
+    fn constrained_triangles(
+        polygon: &Polygon,
+        snap_radius: Option<f64>,
+    ) -> PyResult<Vec<Triangle>> {
+        // geo's default snaps within an absolute 1e-4, which merges the vertices of small or
+        // finely detailed polygons, so the default scales with the polygon instead
+        let snap_radius = match snap_radius {
+            Some(r) if r.is_nan() || r < 0.0 => {
+                return Err(PyValueError::new_err(
+                    "snap_radius must be zero or positive",
+                ));
+            }
+            Some(r) => r,
+            None => match polygon.bounding_rect() {
+                Some(r) => 1e-9 * r.width().max(r.height()),
+                None => 0.0,
+            },
+        };
+        polygon
+            .constrained_triangulation(DelaunayTriangulationConfig { snap_radius })
+            .map_err(|e| PyValueError::new_err(format!("Triangulation failed: {e}")))
+    }
+
+    fn triangles_to_py(
+        py: Python<'_>,
+        triangles: Vec<Triangle>,
+        coords: &[Coord],
+        indices: bool,
+        srid: Option<i32>,
+    ) -> PyResult<Py<PyAny>> {
+        if !indices {
+            let polygons = triangles.iter().map(|t| t.to_polygon()).collect();
+            return shape_to_py(py, Shapes::MultiPolygon(Arc::new(polygons)), srid);
+        }
+        let mut lookup: HashMap<CoordKey, usize> = HashMap::new();
+        coords.iter().enumerate().for_each(|(i, c)| {
+            lookup.entry(coord_key(*c)).or_insert(i);
+        });
+        let mut arr = Array2::zeros((triangles.len(), 3));
+        for (i, triangle) in triangles.iter().enumerate() {
+            for (j, c) in triangle.to_array().iter().enumerate() {
+                arr[[i, j]] = *lookup.get(&coord_key(*c)).ok_or_else(|| {
+                    PyValueError::new_err("Triangulation snapped a vertex away from the input")
+                })?;
+            }
+        }
+        PyArray2::<usize>::from_owned_array(py, arr).into_py_any(py)
+    }
+
+    fn ring_vertices(ring: &LineString) -> &[Coord] {
+        match ring.0.len() {
+            n if n >= 4 && ring.is_closed() => &ring.0[..n - 1],
+            _ => &[],
+        }
+    }
+
+    fn coverage_neighbours(polygons: &[Polygon]) -> HashMap<CoordKey, HashSet<CoordKey>> {
+        let mut neighbours: HashMap<CoordKey, HashSet<CoordKey>> = HashMap::new();
+        polygons
+            .iter()
+            .flat_map(|p| std::iter::once(p.exterior()).chain(p.interiors()))
+            .for_each(|ring| {
+                let vertices = ring_vertices(ring);
+                for i in 0..vertices.len() {
+                    let a = coord_key(vertices[i]);
+                    let b = coord_key(vertices[(i + 1) % vertices.len()]);
+                    if a != b {
+                        neighbours.entry(a).or_default().insert(b);
+                        neighbours.entry(b).or_default().insert(a);
+                    }
+                }
+            });
+        neighbours
+    }
+
+    fn simplify_coverage_ring(
+        ring: &LineString,
+        epsilon: f64,
+        neighbours: &HashMap<CoordKey, HashSet<CoordKey>>,
+        arcs: &mut HashMap<Vec<CoordKey>, Vec<Coord>>,
+    ) -> LineString {
+        let vertices = ring_vertices(ring);
+        if vertices.is_empty() {
+            return ring.clone();
+        }
+        // A vertex without neighbours only occurs when the whole ring is a single repeated
+        // point, which has nothing to simplify
+        let Some(degrees) = vertices
+            .iter()
+            .map(|&c| neighbours.get(&coord_key(c)).map(HashSet::len))
+            .collect::<Option<Vec<usize>>>()
+        else {
+            return ring.clone();
+        };
+        // Arcs run between nodes, the vertices where the set of rings sharing an edge changes
+        let mut nodes = (0..vertices.len())
+            .filter(|&i| degrees[i] != 2)
+            .collect::<Vec<usize>>();
+        if nodes.len() < 2 {
+            // A ring with no shared boundary is split at its smallest vertex and the vertex
+            // furthest from it, so identical rings are cut identically and never collapse to
+            // a single closed arc
+            let first = match nodes.first() {
+                Some(&i) => i,
+                None => (0..vertices.len())
+                    .min_by_key(|&i| coord_key(vertices[i]))
+                    .unwrap_or(0),
+            };
+            let furthest = (0..vertices.len())
+                .max_by(|&i, &j| {
+                    let di = Euclidean.distance(vertices[first], vertices[i]);
+                    let dj = Euclidean.distance(vertices[first], vertices[j]);
+                    di.total_cmp(&dj)
+                })
+                .unwrap_or(first);
+            nodes = vec![first, furthest];
+            nodes.sort();
+            nodes.dedup();
+        }
+
+        let mut coords: Vec<Coord> = Vec::new();
+        for (k, &start) in nodes.iter().enumerate() {
+            let end = nodes[(k + 1) % nodes.len()];
+            let length = (end + vertices.len() - start - 1) % vertices.len() + 1;
+            let arc = (0..=length)
+                .map(|i| vertices[(start + i) % vertices.len()])
+                .collect::<Vec<Coord>>();
+            let keys = arc.iter().map(|&c| coord_key(c)).collect::<Vec<CoordKey>>();
+            let reversed_keys = keys.iter().rev().cloned().collect::<Vec<CoordKey>>();
+            let reversed = reversed_keys < keys;
+            let key = if reversed { reversed_keys } else { keys };
+            let simple_arc = arcs.entry(key).or_insert_with(|| {
+                let mut canonical = arc.clone();
+                if reversed {
+                    canonical.reverse();
+                }
+                LineString::new(canonical).simplify(epsilon).0
+            });
+            let mut simple_arc = simple_arc.clone();
+            if reversed {
+                simple_arc.reverse();
+            }
+            let skip = if coords.is_empty() { 0 } else { 1 };
+            coords.extend(simple_arc.into_iter().skip(skip));
+        }
+        let mut simple_ring = LineString::new(coords);
+        simple_ring.close();
+        simple_ring
+    }
+
+    fn simplify_coverage_polygon(
+        polygon: &Polygon,
+        epsilon: f64,
+        neighbours: &HashMap<CoordKey, HashSet<CoordKey>>,
+        arcs: &mut HashMap<Vec<CoordKey>, Vec<Coord>>,
+    ) -> Option<Polygon> {
+        let exterior = simplify_coverage_ring(polygon.exterior(), epsilon, neighbours, arcs);
+        if exterior.0.len() < 4 {
+            return None;
+        }
+        let interiors = polygon
+            .interiors()
+            .iter()
+            .map(|ring| simplify_coverage_ring(ring, epsilon, neighbours, arcs))
+            .filter(|ring| ring.0.len() >= 4)
+            .collect::<Vec<LineString>>();
+        Some(Polygon::new(exterior, interiors))
+    }
+
+    fn circle_from_two(a: Coord, b: Coord) -> (Coord, f64) {
+        let center = Coord {
+            x: (a.x + b.x) / 2.0,
+            y: (a.y + b.y) / 2.0,
+        };
+        (center, (a.x - b.x).hypot(a.y - b.y) / 2.0)
     }
 
-    #[pyfunction(name = "polygon_polygon_distance")]
-    fn poly_poly_distance_py<'py>(
-        x: PyReadonlyArray2<'py, f64>,
-        y: PyReadonlyArray2<'py, f64>,
-    ) -> f64 {
-        let path_x = x
-            .as_array()
-            .axis_iter(Axis(0))
-            .map(|x| Point::new(x[0], x[1]))
-            .collect::<LineString>();
-        let path_y = y
-            .as_array()
-            .axis_iter(Axis(0))
-            .map(|x| Point::new(x[0], x[1]))
-            .collect::<LineString>();
-        let distance = Euclidean.distance(&path_x, &path_y);
-        distance
+    fn circle_from_three(a: Coord, b: Coord, c: Coord) -> (Coord, f64) {
+        let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+        if d == 0.0 {
+            // Collinear points, the circle spans the two furthest apart
+            return [
+                circle_from_two(a, b),
+                circle_from_two(a, c),
+                circle_from_two(b, c),
+            ]
+            .into_iter()
+            .fold((a, 0.0), |best, x| if x.1 > best.1 { x } else { best });
+        }
+        let a2 = a.x * a.x + a.y * a.y;
+        let b2 = b.x * b.x + b.y * b.y;
+        let c2 = c.x * c.x + c.y * c.y;
+        let center = Coord {
+            x: (a2 * (b.y - c.y) + b2 * (c.y - a.y) + c2 * (a.y - b.y)) / d,
+            y: (a2 * (c.x - b.x) + b2 * (a.x - c.x) + c2 * (b.x - a.x)) / d,
+        };
+        (center, (a.x - center.x).hypot(a.y - center.y))
     }
 
-    #[pyfunction(name = "points_polygon_dist_mut")]
-    fn points_poly_distance_mut_py<'py>(
-        py: Python<'py>,
-        x: PyReadonlyArray2<'py, f64>,
-        y: PyReadonlyArray2<'py, f64>,
-    ) -> Bound<'py, PyArray1<f64>> {
-        let x = x.as_array();
-        let y = y.as_array();
-        let distances_vec = x
-            .axis_iter(Axis(0))
-            .into_par_iter()
-            .map(|p| point_poly_distance(p, y))
-            .collect::<Vec<f64>>();
-        distances_vec.into_pyarray(py)
+    fn minimum_bounding_circle(coords: &[Coord]) -> Option<(Coord, f64)> {
+        let outside = |circle: (Coord, f64), p: Coord| {
+            (p.x - circle.0.x).hypot(p.y - circle.0.y) > circle.1 * (1.0 + 1e-12)
+        };
+        let mut circle = (*coords.first()?, 0.0);
+        for i in 1..coords.len() {
+            if outside(circle, coords[i]) {
+                circle = (coords[i], 0.0);
+                for j in 0..i {
+                    if outside(circle, coords[j]) {
+                        circle = circle_from_two(coords[i], coords[j]);
+                        for k in 0..j {
+                            if outside(circle, coords[k]) {
+                                circle = circle_from_three(coords[i], coords[j], coords[k]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Some(circle)
     }
 
+    fn circle_polygon(center: Coord, radius: f64) -> Polygon {
+        let segments = 64;
+        let ring = (0..=segments)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * (i % segments) as f64 / segments as f64;
+                Coord {
+                    x: center.x + radius * angle.cos(),
+                    y: center.y + radius * angle.sin(),
+                }
+            })
+            .collect::<LineString>();
+        Polygon::new(ring, vec![])
+    }
+
+    type PolygonArrays<'py> = (PyReadonlyArray2<'py, f64>, Vec<PyReadonlyArray2<'py, f64>>);
+    type PyPolygonArrays<'py> = (Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>);
+
     fn array2_to_linestring<'py>(x: &PyReadonlyArray2<'py, f64>) -> LineString {
         assert_eq!(x.shape()[1], 2, "Y dimension not equal to 2");
-        let path = x
-            .as_array()
+        x.as_array()
             .axis_iter(Axis(0))
             .map(|y| Point::new(y[0], y[1]))
-            .collect::<LineString>();
-        path
+            .collect::<LineString>()
     }
 
     fn array2_to_polygon<'py>(
         x: &PyReadonlyArray2<'py, f64>,
         ys: &Vec<PyReadonlyArray2<'py, f64>>,
     ) -> Polygon {
-        let exterior = array2_to_linestring(&x);
+        let exterior = array2_to_linestring(x);
         let interiors = ys
             .iter()
             .map(|y| array2_to_linestring(y))
@@ -114,11 +1638,10 @@ mod rust_geo_python {
 
     fn linestring_to_pyarray2<'py>(py: Python<'py>, ls: &LineString) -> Bound<'py, PyArray2<f64>> {
         let arr = linestring_to_array(ls);
-        let pyarray = PyArray2::from_owned_array(py, arr);
-        pyarray
+        PyArray2::from_owned_array(py, arr)
     }
 
-    fn linestring_to_array<'py>(ls: &LineString) -> Array2<f64> {
+    fn linestring_to_array(ls: &LineString) -> Array2<f64> {
         let n_points = ls.points().len();
         let mut arr = Array2::zeros((n_points, 2));
         let mut i = 0;
@@ -131,7 +1654,7 @@ mod rust_geo_python {
         arr
     }
 
-    fn multipoint_to_array<'py>(mp: &MultiPoint) -> Array2<f64> {
+    fn multipoint_to_array(mp: &MultiPoint) -> Array2<f64> {
         let n_points = mp.len();
         let mut arr = Array2::zeros((n_points, 2));
         let mut i = 0;
@@ -147,7 +1670,7 @@ mod rust_geo_python {
     fn polygons_to_array2<'py>(
         py: Python<'py>,
         polygons: Vec<&Polygon>,
-    ) -> Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)> {
+    ) -> Vec<PyPolygonArrays<'py>> {
         polygons
             .iter()
             .map(|p| {
@@ -160,13 +1683,10 @@ mod rust_geo_python {
                     .collect::<Vec<Bound<'py, PyArray2<f64>>>>();
                 (ext_array, int_arrays)
             })
-            .collect::<Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)>>()
+            .collect::<Vec<PyPolygonArrays<'py>>>()
     }
 
-    fn polygon_to_array2<'py>(
-        py: Python<'py>,
-        polygon: &Polygon,
-    ) -> (Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>) {
+    fn polygon_to_array2<'py>(py: Python<'py>, polygon: &Polygon) -> PyPolygonArrays<'py> {
         let ext = polygon.exterior();
         let ext_array = linestring_to_pyarray2(py, ext);
         let int_arrays = polygon
@@ -178,40 +1698,78 @@ mod rust_geo_python {
     }
 
     #[pyfunction]
+    #[pyo3(signature = (pyarrays, grid_size=None))]
     fn union_set_shapes<'py>(
         py: Python<'py>,
-        pyarrays: Vec<(PyReadonlyArray2<'py, f64>, Vec<PyReadonlyArray2<'py, f64>>)>,
-    ) -> Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)> {
-        let polygons = pyarrays
+        pyarrays: Vec<PolygonArrays<'py>>,
+        grid_size: Option<f64>,
+    ) -> PyResult<Vec<PyPolygonArrays<'py>>> {
+        let mut polygons = pyarrays
             .iter()
             .map(|(x, ys)| array2_to_polygon(x, ys))
             .collect::<Vec<Polygon>>();
-        let union = unary_union(&polygons);
-        polygons_to_array2(py, union.iter().collect::<Vec<&Polygon>>())
+        if let Some(grid_size) = grid_size {
+            check_grid_size(grid_size)?;
+            polygons = set_precision_polygons(&polygons, grid_size).0;
+        }
+        let mut union = unary_union(&polygons);
+        if let Some(grid_size) = grid_size {
+            union = set_precision_polygons(&union.0, grid_size);
+        }
+        Ok(polygons_to_array2(
+            py,
+            union.iter().collect::<Vec<&Polygon>>(),
+        ))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (pyarray_x, pyarray_y, grid_size=None))]
     fn intersection_shapes<'py>(
         py: Python<'py>,
-        pyarray_x: (PyReadonlyArray2<'py, f64>, Vec<PyReadonlyArray2<'py, f64>>),
-        pyarray_y: (PyReadonlyArray2<'py, f64>, Vec<PyReadonlyArray2<'py, f64>>),
-    ) -> Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)> {
+        pyarray_x: PolygonArrays<'py>,
+        pyarray_y: PolygonArrays<'py>,
+        grid_size: Option<f64>,
+    ) -> PyResult<Vec<PyPolygonArrays<'py>>> {
         let polygon_x = array2_to_polygon(&pyarray_x.0, &pyarray_x.1);
         let polygon_y = array2_to_polygon(&pyarray_y.0, &pyarray_y.1);
-        let intersection = polygon_x.intersection(&polygon_y);
-        polygons_to_array2(py, intersection.iter().collect::<Vec<&Polygon>>())
+        let intersection = match grid_size {
+            Some(grid_size) => {
+                check_grid_size(grid_size)?;
+                let x = set_precision_polygons(&[polygon_x], grid_size);
+                let y = set_precision_polygons(&[polygon_y], grid_size);
+                set_precision_polygons(&x.intersection(&y).0, grid_size)
+            }
+            None => polygon_x.intersection(&polygon_y),
+        };
+        Ok(polygons_to_array2(
+            py,
+            intersection.iter().collect::<Vec<&Polygon>>(),
+        ))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (pyarray_x, pyarray_y, grid_size=None))]
     fn difference_shapes<'py>(
         py: Python<'py>,
-        pyarray_x: (PyReadonlyArray2<'py, f64>, Vec<PyReadonlyArray2<'py, f64>>),
-        pyarray_y: (PyReadonlyArray2<'py, f64>, Vec<PyReadonlyArray2<'py, f64>>),
-    ) -> Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)> {
+        pyarray_x: PolygonArrays<'py>,
+        pyarray_y: PolygonArrays<'py>,
+        grid_size: Option<f64>,
+    ) -> PyResult<Vec<PyPolygonArrays<'py>>> {
         let polygon_x = array2_to_polygon(&pyarray_x.0, &pyarray_x.1);
         let polygon_y = array2_to_polygon(&pyarray_y.0, &pyarray_y.1);
-        let intersection = polygon_x.difference(&polygon_y);
-        polygons_to_array2(py, intersection.iter().collect::<Vec<&Polygon>>())
+        let intersection = match grid_size {
+            Some(grid_size) => {
+                check_grid_size(grid_size)?;
+                let x = set_precision_polygons(&[polygon_x], grid_size);
+                let y = set_precision_polygons(&[polygon_y], grid_size);
+                set_precision_polygons(&x.difference(&y).0, grid_size)
+            }
+            None => polygon_x.difference(&polygon_y),
+        };
+        Ok(polygons_to_array2(
+            py,
+            intersection.iter().collect::<Vec<&Polygon>>(),
+        ))
     }
 
     #[derive(Clone)]
@@ -228,6 +1786,7 @@ mod rust_geo_python {
     #[derive(Clone)]
     struct Shape {
         inner: Shapes,
+        srid: Option<i32>,
     }
 
     #[pyclass(extends=Shape)]
@@ -259,20 +1818,394 @@ mod rust_geo_python {
         multipolygon: Arc<MultiPolygon>,
     }
 
+    fn shape_to_py(py: Python<'_>, inner: Shapes, srid: Option<i32>) -> PyResult<Py<PyAny>> {
+        let object = match inner {
+            Shapes::Point(p) => Py::new(
+                py,
+                (
+                    RustPoint { point: p.clone() },
+                    Shape {
+                        inner: Shapes::Point(p),
+                        srid,
+                    },
+                ),
+            )?
+            .into_any(),
+            Shapes::MultiPoint(p) => Py::new(
+                py,
+                (
+                    RustMultiPoint {
+                        multipoint: p.clone(),
+                    },
+                    Shape {
+                        inner: Shapes::MultiPoint(p),
+                        srid,
+                    },
+                ),
+            )?
+            .into_any(),
+            Shapes::LineString(p) => Py::new(
+                py,
+                (
+                    RustLineString {
+                        linestring: p.clone(),
+                    },
+                    Shape {
+                        inner: Shapes::LineString(p),
+                        srid,
+                    },
+                ),
+            )?
+            .into_any(),
+            Shapes::MultiLineString(p) => Py::new(
+                py,
+                (
+                    RustMultiLineString {
+                        multilinestring: p.clone(),
+                    },
+                    Shape {
+                        inner: Shapes::MultiLineString(p),
+                        srid,
+                    },
+                ),
+            )?
+            .into_any(),
+            Shapes::Polygon(p) => Py::new(
+                py,
+                (
+                    RustPolygon { polygon: p.clone() },
+                    Shape {
+                        inner: Shapes::Polygon(p),
+                        srid,
+                    },
+                ),
+            )?
+            .into_any(),
+            Shapes::MultiPolygon(p) => Py::new(
+                py,
+                (
+                    RustMultiPolygon {
+                        multipolygon: p.clone(),
+                    },
+                    Shape {
+                        inner: Shapes::MultiPolygon(p),
+                        srid,
+                    },
+                ),
+            )?
+            .into_any(),
+        };
+        Ok(object)
+    }
+
+    fn validation_errors<G: Validation>(geometry: &G) -> Vec<String> {
+        geometry
+            .validation_errors()
+            .iter()
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    fn shape_validation_errors(shape: &Shapes) -> Vec<String> {
+        match shape {
+            Shapes::Point(p) => validation_errors(p.as_ref()),
+            Shapes::MultiPoint(p) => validation_errors(p.as_ref()),
+            Shapes::LineString(p) => validation_errors(p.as_ref()),
+            Shapes::MultiLineString(p) => validation_errors(p.as_ref()),
+            Shapes::Polygon(p) => validation_errors(p.as_ref()),
+            Shapes::MultiPolygon(p) => validation_errors(p.as_ref()),
+        }
+    }
+
+    fn check_validity<G: Validation>(geometry: &G) -> PyResult<()> {
+        let errors = validation_errors(geometry);
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(PyValueError::new_err(format!(
+            "Invalid geometry: {}",
+            errors.join("; ")
+        )))
+    }
+
+    fn shape_linestring(shape: &Shapes, name: &str) -> PyResult<Arc<LineString>> {
+        match shape {
+            Shapes::LineString(p) => Ok(p.clone()),
+            _ => Err(PyValueError::new_err(format!(
+                "{name} is only defined between RustLineString shapes"
+            ))),
+        }
+    }
+
+    fn shape_polygons(shape: &Shapes, name: &str) -> PyResult<Vec<Polygon>> {
+        match shape {
+            Shapes::Polygon(p) => Ok(vec![p.as_ref().clone()]),
+            Shapes::MultiPolygon(p) => Ok(p.0.clone()),
+            _ => Err(PyValueError::new_err(format!(
+                "{name} expects RustPolygon or RustMultiPolygon shapes"
+            ))),
+        }
+    }
+
+    fn make_valid_polygons(polygons: &[Polygon]) -> MultiPolygon {
+        // overlaying against nothing rebuilds each polygon with an even-odd fill, splitting
+        // bow-ties and turning mis-nested holes into separate parts
+        let empty = MultiPolygon::new(vec![]);
+        let repaired = polygons
+            .iter()
+            .map(|p| p.union(&empty))
+            .collect::<Vec<MultiPolygon>>();
+        match repaired.len() {
+            1 => repaired.into_iter().next().unwrap(),
+            _ => unary_union(repaired.iter().flatten()),
+        }
+    }
+
+    fn collapsed_linework(polygons: &[Polygon], repaired: &MultiPolygon) -> MultiLineString {
+        let boundary = RTree::bulk_load(repaired.lines_iter().collect::<Vec<Line>>());
+        let tolerance = match repaired.bounding_rect() {
+            Some(r) => 1e-9 * r.width().max(r.height()).max(1.0),
+            None => 1e-9,
+        };
+        let mut collapsed = vec![];
+        for ring in polygons
+            .iter()
+            .flat_map(|p| std::iter::once(p.exterior()).chain(p.interiors()))
+        {
+            let mut current: Vec<Coord> = vec![];
+            for line in ring.lines() {
+                let midpoint = Point::from((line.start + line.end) / 2.0);
+                let on_boundary = boundary
+                    .locate_within_distance(midpoint, tolerance * tolerance)
+                    .next()
+                    .is_some();
+                if !on_boundary {
+                    if current.is_empty() {
+                        current.push(line.start);
+                    }
+                    current.push(line.end);
+                } else if !current.is_empty() {
+                    collapsed.push(LineString::new(std::mem::take(&mut current)));
+                }
+            }
+            if !current.is_empty() {
+                collapsed.push(LineString::new(current));
+            }
+        }
+        MultiLineString::new(collapsed)
+    }
+
+    fn check_grid_size(grid_size: f64) -> PyResult<()> {
+        if grid_size.is_nan() || grid_size < 0.0 {
+            return Err(PyValueError::new_err("grid_size must be zero or positive"));
+        }
+        Ok(())
+    }
+
+    fn snap_coord(c: Coord, size: f64) -> Coord {
+        Coord {
+            x: (c.x / size).round() * size,
+            y: (c.y / size).round() * size,
+        }
+    }
+
+    fn snap_shape(shape: &Shapes, size: f64) -> Shapes {
+        if size == 0.0 {
+            return shape.clone();
+        }
+        let snap = |c: Coord| snap_coord(c, size);
+        match shape {
+            Shapes::Point(p) => Shapes::Point(Arc::new(p.map_coords(snap))),
+            Shapes::MultiPoint(p) => {
+                Shapes::MultiPoint(Arc::new(p.map_coords(snap).remove_repeated_points()))
+            }
+            Shapes::LineString(p) => {
+                Shapes::LineString(Arc::new(p.map_coords(snap).remove_repeated_points()))
+            }
+            Shapes::MultiLineString(p) => {
+                Shapes::MultiLineString(Arc::new(p.map_coords(snap).remove_repeated_points()))
+            }
+            Shapes::Polygon(p) => {
+                Shapes::Polygon(Arc::new(p.map_coords(snap).remove_repeated_points()))
+            }
+            Shapes::MultiPolygon(p) => {
+                Shapes::MultiPolygon(Arc::new(p.map_coords(snap).remove_repeated_points()))
+            }
+        }
+    }
+
+    fn set_precision_polygons(polygons: &[Polygon], grid_size: f64) -> MultiPolygon {
+        if grid_size == 0.0 {
+            return MultiPolygon::new(polygons.to_vec());
+        }
+        let snapped = polygons
+            .iter()
+            .map(|p| {
+                p.map_coords(|c| snap_coord(c, grid_size))
+                    .remove_repeated_points()
+            })
+            .collect::<Vec<Polygon>>();
+        make_valid_polygons(&snapped)
+    }
+
+    fn set_precision_shape(shape: &Shapes, grid_size: f64) -> Shapes {
+        match snap_shape(shape, grid_size) {
+            Shapes::LineString(p) if p.0.len() < 2 => {
+                Shapes::LineString(Arc::new(LineString::new(vec![])))
+            }
+            Shapes::MultiLineString(p) => Shapes::MultiLineString(Arc::new(
+                p.iter().filter(|ls| ls.0.len() > 1).cloned().collect(),
+            )),
+            Shapes::Polygon(p) => {
+                let mut polygons = set_precision_polygons(&[p.as_ref().clone()], grid_size).0;
+                match polygons.len() {
+                    1 => Shapes::Polygon(Arc::new(polygons.remove(0))),
+                    _ => Shapes::MultiPolygon(Arc::new(MultiPolygon::new(polygons))),
+                }
+            }
+            Shapes::MultiPolygon(p) => {
+                Shapes::MultiPolygon(Arc::new(set_precision_polygons(&p.0, grid_size)))
+            }
+            snapped => snapped,
+        }
+    }
+
+    fn dedup_coords(coords: &[Coord], tolerance: f64) -> Vec<Coord> {
+        let mut kept: Vec<Coord> = Vec::with_capacity(coords.len());
+        for c in coords {
+            match kept.last() {
+                Some(last) if Euclidean.distance(*last, *c) <= tolerance => {}
+                _ => kept.push(*c),
+            }
+        }
+        kept
+    }
+
+    fn dedup_linestring(linestring: &LineString, tolerance: f64) -> LineString {
+        let mut coords = dedup_coords(&linestring.0, tolerance);
+        // keep the final vertex so lines still end where they did
+        if coords.len() > 1 {
+            let n = coords.len();
+            coords[n - 1] = linestring.0[linestring.0.len() - 1];
+        }
+        LineString::new(coords)
+    }
+
+    fn dedup_ring(ring: &LineString, tolerance: f64) -> LineString {
+        let open = &ring.0[..ring.0.len().saturating_sub(1)];
+        let mut coords = dedup_coords(open, tolerance);
+        while coords.len() > 1
+            && Euclidean.distance(coords[0], coords[coords.len() - 1]) <= tolerance
+        {
+            coords.pop();
+        }
+        LineString::new(coords)
+    }
+
+    fn dedup_polygon(polygon: &Polygon, tolerance: f64) -> Polygon {
+        Polygon::new(
+            dedup_ring(polygon.exterior(), tolerance),
+            polygon
+                .interiors()
+                .iter()
+                .map(|x| dedup_ring(x, tolerance))
+                .collect(),
+        )
+    }
+
+    fn dedup_points(points: &[Point], tolerance: f64) -> Vec<Point> {
+        if tolerance == 0.0 {
+            let mut seen: HashSet<CoordKey> = HashSet::new();
+            return points
+                .iter()
+                .filter(|p| seen.insert(coord_key(p.0)))
+                .copied()
+                .collect();
+        }
+        // Bucket kept points on a grid of `tolerance` sized cells, so a point only needs
+        // comparing with the kept points in its own and the eight surrounding cells
+        let cell = |p: &Point| {
+            (
+                (p.x() / tolerance).floor() as i64,
+                (p.y() / tolerance).floor() as i64,
+            )
+        };
+        let mut grid: HashMap<(i64, i64), Vec<Point>> = HashMap::new();
+        let mut kept: Vec<Point> = vec![];
+        for point in points {
+            let (i, j) = cell(point);
+            let repeated = (i.saturating_sub(1)..=i.saturating_add(1))
+                .flat_map(|x| (j.saturating_sub(1)..=j.saturating_add(1)).map(move |y| (x, y)))
+                .filter_map(|key| grid.get(&key))
+                .flatten()
+                .any(|x| Euclidean.distance(x, point) <= tolerance);
+            if !repeated {
+                grid.entry((i, j)).or_default().push(*point);
+                kept.push(*point);
+            }
+        }
+        kept
+    }
+
+    fn remove_repeated_points(shape: &Shapes, tolerance: f64) -> Shapes {
+        match shape {
+            Shapes::Point(_) => shape.clone(),
+            Shapes::MultiPoint(p) => {
+                Shapes::MultiPoint(Arc::new(MultiPoint::new(dedup_points(&p.0, tolerance))))
+            }
+            Shapes::LineString(p) => Shapes::LineString(Arc::new(dedup_linestring(p, tolerance))),
+            Shapes::MultiLineString(p) => Shapes::MultiLineString(Arc::new(
+                p.iter().map(|x| dedup_linestring(x, tolerance)).collect(),
+            )),
+            Shapes::Polygon(p) => Shapes::Polygon(Arc::new(dedup_polygon(p, tolerance))),
+            Shapes::MultiPolygon(p) => Shapes::MultiPolygon(Arc::new(
+                p.iter().map(|x| dedup_polygon(x, tolerance)).collect(),
+            )),
+        }
+    }
+
+    fn common_srid(lhs: Option<i32>, rhs: Option<i32>) -> PyResult<Option<i32>> {
+        match (lhs, rhs) {
+            (Some(a), Some(b)) if a != b => Err(PyValueError::new_err(format!(
+                "Cannot combine shapes with different SRIDs {a} and {b}"
+            ))),
+            _ => Ok(lhs.or(rhs)),
+        }
+    }
+
+    fn parse_direction(direction: &str) -> PyResult<Direction> {
+        match direction {
+            "default" => Ok(Direction::Default),
+            "reversed" => Ok(Direction::Reversed),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown direction '{direction}', expected 'default' or 'reversed'"
+            ))),
+        }
+    }
+
     #[pymethods]
     impl RustLineString {
         #[new]
-        fn new(x: PyReadonlyArray2<f64>) -> (Self, Shape) {
+        #[pyo3(signature = (x, srid=None, validate=false))]
+        fn new(
+            x: PyReadonlyArray2<f64>,
+            srid: Option<i32>,
+            validate: bool,
+        ) -> PyResult<(Self, Shape)> {
             let ls = array2_to_linestring(&x);
+            if validate {
+                check_validity(&ls)?;
+            }
             let ls_arc = Arc::new(ls);
-            (
+            Ok((
                 RustLineString {
                     linestring: ls_arc.clone(),
                 },
                 Shape {
                     inner: Shapes::LineString(ls_arc),
+                    srid,
                 },
-            )
+            ))
         }
 
         fn xy<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
@@ -280,25 +2213,125 @@ mod rust_geo_python {
             let pyarray = PyArray2::from_owned_array(py, arr);
             Ok(pyarray)
         }
+
+        #[pyo3(signature = (epsilon, algorithm="rdp"))]
+        fn simplify<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            epsilon: f64,
+            algorithm: &str,
+        ) -> PyResult<Py<PyAny>> {
+            let algorithm = parse_simplify_algorithm(algorithm)?;
+            let simple_linestring = simplify_geometry(slf.linestring.as_ref(), epsilon, algorithm);
+            shape_to_py(
+                py,
+                Shapes::LineString(Arc::new(simple_linestring)),
+                slf.as_super().srid,
+            )
+        }
+
+        fn is_ccw(&self) -> bool {
+            self.linestring.is_ccw()
+        }
+
+        fn is_cw(&self) -> bool {
+            self.linestring.is_cw()
+        }
+
+        #[pyo3(signature = (point, normalized=false))]
+        fn line_locate_point(
+            slf: PyRef<'_, Self>,
+            point: PointLike<'_>,
+            normalized: bool,
+        ) -> PyResult<Option<f64>> {
+            let point = match point {
+                PointLike::Point(p) => {
+                    common_srid(slf.as_super().srid, p.as_super().srid)?;
+                    *p.point
+                }
+                PointLike::Coord((x, y)) => Point::new(x, y),
+            };
+            let fraction = slf.linestring.line_locate_point(&point);
+            match normalized {
+                true => Ok(fraction),
+                false => Ok(fraction.map(|x| x * Euclidean.length(slf.linestring.as_ref()))),
+            }
+        }
+
+        #[pyo3(signature = (distance, normalized=false))]
+        fn interpolate<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            distance: f64,
+            normalized: bool,
+        ) -> PyResult<Py<PyAny>> {
+            match line_interpolate(&slf.linestring, distance, normalized) {
+                Some(p) => shape_to_py(py, Shapes::Point(Arc::new(p)), slf.as_super().srid),
+                None => Ok(py.None()),
+            }
+        }
+
+        #[pyo3(signature = (start, end, normalized=false))]
+        fn substring<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            start: f64,
+            end: f64,
+            normalized: bool,
+        ) -> PyResult<Py<PyAny>> {
+            let start = line_fraction(&slf.linestring, start, normalized);
+            let end = line_fraction(&slf.linestring, end, normalized);
+            let substring = line_substring(&slf.linestring, start, end);
+            shape_to_py(
+                py,
+                Shapes::LineString(Arc::new(substring)),
+                slf.as_super().srid,
+            )
+        }
+
+        fn interpolate_many<'py>(
+            &self,
+            py: Python<'py>,
+            fractions: PyReadonlyArray1<'py, f64>,
+        ) -> Bound<'py, PyArray2<f64>> {
+            let fractions = fractions.as_array();
+            let mut arr = Array2::from_elem((fractions.len(), 2), f64::NAN);
+            fractions.iter().enumerate().for_each(|(i, fraction)| {
+                if let Some(p) = line_interpolate(&self.linestring, *fraction, true) {
+                    arr[[i, 0]] = p.x();
+                    arr[[i, 1]] = p.y();
+                }
+            });
+            PyArray2::from_owned_array(py, arr)
+        }
     }
 
     #[pymethods]
     impl RustMultiPoint {
         #[new]
-        fn new(x: PyReadonlyArray2<f64>) -> (Self, Shape) {
+        #[pyo3(signature = (x, srid=None, validate=false))]
+        fn new(
+            x: PyReadonlyArray2<f64>,
+            srid: Option<i32>,
+            validate: bool,
+        ) -> PyResult<(Self, Shape)> {
             let ls = array2_to_linestring(&x);
 
             let multipoint = ls.points().collect::<MultiPoint>();
+            if validate {
+                check_validity(&multipoint)?;
+            }
             let multipoint_arc = Arc::new(multipoint);
 
-            (
+            Ok((
                 RustMultiPoint {
                     multipoint: multipoint_arc.clone(),
                 },
                 Shape {
                     inner: Shapes::MultiPoint(multipoint_arc),
+                    srid,
                 },
-            )
+            ))
         }
 
         fn xy<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
@@ -311,17 +2344,22 @@ mod rust_geo_python {
     #[pymethods]
     impl RustPoint {
         #[new]
-        fn new(x: f64, y: f64) -> (Self, Shape) {
+        #[pyo3(signature = (x, y, srid=None, validate=false))]
+        fn new(x: f64, y: f64, srid: Option<i32>, validate: bool) -> PyResult<(Self, Shape)> {
             let point = Point::new(x, y);
+            if validate {
+                check_validity(&point)?;
+            }
             let point_arc = Arc::new(point);
-            (
+            Ok((
                 RustPoint {
                     point: point_arc.clone(),
                 },
                 Shape {
                     inner: Shapes::Point(point_arc),
+                    srid,
                 },
-            )
+            ))
         }
 
         fn xy<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
@@ -333,62 +2371,114 @@ mod rust_geo_python {
     #[pymethods]
     impl RustPolygon {
         #[new]
-        fn new(x: PyReadonlyArray2<f64>, ys: Vec<PyReadonlyArray2<f64>>) -> (Self, Shape) {
-            let polygon = array2_to_polygon(&x, &ys).orient(Direction::Default);
+        #[pyo3(signature = (x, ys, orient=true, srid=None, validate=false))]
+        fn new(
+            x: PyReadonlyArray2<f64>,
+            ys: Vec<PyReadonlyArray2<f64>>,
+            orient: bool,
+            srid: Option<i32>,
+            validate: bool,
+        ) -> PyResult<(Self, Shape)> {
+            let mut polygon = array2_to_polygon(&x, &ys);
+            if orient {
+                polygon = polygon.orient(Direction::Default);
+            }
+            if validate {
+                check_validity(&polygon)?;
+            }
             let polygon_arc = Arc::new(polygon);
-            (
+            Ok((
                 RustPolygon {
                     polygon: polygon_arc.clone(),
                 },
                 Shape {
                     inner: Shapes::Polygon(polygon_arc),
+                    srid,
                 },
-            )
+            ))
         }
 
-        fn xy<'py>(
-            &self,
-            py: Python<'py>,
-        ) -> PyResult<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)> {
+        fn xy<'py>(&self, py: Python<'py>) -> PyResult<PyPolygonArrays<'py>> {
             Ok(polygon_to_array2(py, self.polygon.as_ref()))
         }
 
-        fn simplify<'py>(&self, py: Python<'py>, epsilon: f64) -> PyResult<Py<PyAny>> {
-            let simple_polygon = self.polygon.simplify(epsilon);
-            let polygon_arc = Arc::new(simple_polygon);
-            let initializer: PyClassInitializer<RustPolygon> = PyClassInitializer::from((
-                RustPolygon {
-                    polygon: polygon_arc.clone(),
-                },
-                Shape {
-                    inner: Shapes::Polygon(polygon_arc),
-                },
-            ));
-            Ok(Py::new(py, initializer)?.into_any())
+        fn exterior_is_ccw(&self) -> bool {
+            self.polygon.exterior().is_ccw()
         }
 
-        fn area(&self) -> f64 {
-            self.polygon.signed_area()
+        fn exterior_is_cw(&self) -> bool {
+            self.polygon.exterior().is_cw()
+        }
+
+        #[pyo3(signature = (epsilon, algorithm="rdp"))]
+        fn simplify<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            epsilon: f64,
+            algorithm: &str,
+        ) -> PyResult<Py<PyAny>> {
+            let algorithm = parse_simplify_algorithm(algorithm)?;
+            let simple_polygon = simplify_geometry(slf.polygon.as_ref(), epsilon, algorithm);
+            shape_to_py(
+                py,
+                Shapes::Polygon(Arc::new(simple_polygon)),
+                slf.as_super().srid,
+            )
+        }
+
+        fn triangulate_earcut<'py>(&self, py: Python<'py>) -> PyResult<EarcutArrays<'py>> {
+            earcut_arrays(py, std::slice::from_ref(self.polygon.as_ref()))
+        }
+
+        #[pyo3(signature = (indices=false, snap_radius=None))]
+        fn constrained_delaunay_triangles<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            indices: bool,
+            snap_radius: Option<f64>,
+        ) -> PyResult<Py<PyAny>> {
+            let triangles = constrained_triangles(&slf.polygon, snap_radius)?;
+            let coords = slf.polygon.coords_iter().collect::<Vec<Coord>>();
+            triangles_to_py(py, triangles, &coords, indices, slf.as_super().srid)
+        }
+
+        #[pyo3(signature = (direction="default"))]
+        fn orient<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            direction: &str,
+        ) -> PyResult<Py<PyAny>> {
+            let polygon = slf.polygon.orient(parse_direction(direction)?);
+            shape_to_py(py, Shapes::Polygon(Arc::new(polygon)), slf.as_super().srid)
         }
     }
 
     #[pymethods]
     impl RustMultiLineString {
         #[new]
-        fn new(ys: Vec<PyReadonlyArray2<f64>>) -> (Self, Shape) {
+        #[pyo3(signature = (ys, srid=None, validate=false))]
+        fn new(
+            ys: Vec<PyReadonlyArray2<f64>>,
+            srid: Option<i32>,
+            validate: bool,
+        ) -> PyResult<(Self, Shape)> {
             let lss = ys
                 .iter()
                 .map(|x| array2_to_linestring(x))
                 .collect::<MultiLineString>();
+            if validate {
+                check_validity(&lss)?;
+            }
             let lss_arc = Arc::new(lss);
-            (
+            Ok((
                 RustMultiLineString {
                     multilinestring: lss_arc.clone(),
                 },
                 Shape {
                     inner: Shapes::MultiLineString(lss_arc),
+                    srid,
                 },
-            )
+            ))
         }
 
         fn xy<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyArray2<f64>>>> {
@@ -399,65 +2489,120 @@ mod rust_geo_python {
                 .collect::<Vec<Bound<'py, PyArray2<f64>>>>();
             Ok(pyarrays)
         }
+
+        #[pyo3(signature = (epsilon, algorithm="rdp"))]
+        fn simplify<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            epsilon: f64,
+            algorithm: &str,
+        ) -> PyResult<Py<PyAny>> {
+            let algorithm = parse_simplify_algorithm(algorithm)?;
+            let simple_multilinestring =
+                simplify_geometry(slf.multilinestring.as_ref(), epsilon, algorithm);
+            shape_to_py(
+                py,
+                Shapes::MultiLineString(Arc::new(simple_multilinestring)),
+                slf.as_super().srid,
+            )
+        }
     }
 
     #[pymethods]
     impl RustMultiPolygon {
         #[new]
+        #[pyo3(signature = (pyarrays, orient=true, srid=None, validate=false))]
         fn new(
             pyarrays: Vec<(PyReadonlyArray2<f64>, Vec<PyReadonlyArray2<f64>>)>,
-        ) -> (Self, Shape) {
+            orient: bool,
+            srid: Option<i32>,
+            validate: bool,
+        ) -> PyResult<(Self, Shape)> {
             let polygons = pyarrays
                 .iter()
-                .map(|(x, ys)| array2_to_polygon(&x, &ys).orient(Direction::Default))
+                .map(|(x, ys)| array2_to_polygon(x, ys))
+                .map(|p| {
+                    if orient {
+                        p.orient(Direction::Default)
+                    } else {
+                        p
+                    }
+                })
                 .collect::<Vec<Polygon>>();
             let multipolygon = MultiPolygon(polygons);
+            if validate {
+                check_validity(&multipolygon)?;
+            }
             let multipolygon_arc = Arc::new(multipolygon);
-            (
+            Ok((
                 RustMultiPolygon {
                     multipolygon: multipolygon_arc.clone(),
                 },
                 Shape {
                     inner: Shapes::MultiPolygon(multipolygon_arc),
+                    srid,
                 },
-            )
+            ))
         }
 
-        fn xy<'py>(
-            &self,
-            py: Python<'py>,
-        ) -> PyResult<Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)>> {
+        fn xy<'py>(&self, py: Python<'py>) -> PyResult<Vec<PyPolygonArrays<'py>>> {
             let result_vec = self
                 .multipolygon
                 .iter()
                 .map(|x| polygon_to_array2(py, x))
-                .collect::<Vec<(Bound<'py, PyArray2<f64>>, Vec<Bound<'py, PyArray2<f64>>>)>>();
+                .collect::<Vec<PyPolygonArrays<'py>>>();
             Ok(result_vec)
         }
 
-        fn simplify<'py>(&self, py: Python<'py>, epsilon: f64) -> PyResult<Py<PyAny>> {
-            let simple_polygon = self.multipolygon.simplify(epsilon);
-            let multipolygon_arc = Arc::new(simple_polygon);
-            let initializer: PyClassInitializer<RustMultiPolygon> = PyClassInitializer::from((
-                RustMultiPolygon {
-                    multipolygon: multipolygon_arc.clone(),
-                },
-                Shape {
-                    inner: Shapes::MultiPolygon(multipolygon_arc),
-                },
-            ));
-            Ok(Py::new(py, initializer)?.into_any())
+        #[pyo3(signature = (epsilon, algorithm="rdp"))]
+        fn simplify<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            epsilon: f64,
+            algorithm: &str,
+        ) -> PyResult<Py<PyAny>> {
+            let algorithm = parse_simplify_algorithm(algorithm)?;
+            let simple_polygon = simplify_geometry(slf.multipolygon.as_ref(), epsilon, algorithm);
+            shape_to_py(
+                py,
+                Shapes::MultiPolygon(Arc::new(simple_polygon)),
+                slf.as_super().srid,
+            )
         }
 
-        fn area(&self) -> f64 {
-            self.multipolygon.signed_area()
+        fn triangulate_earcut<'py>(&self, py: Python<'py>) -> PyResult<EarcutArrays<'py>> {
+            earcut_arrays(py, &self.multipolygon.0)
+        }
+
+        #[pyo3(signature = (direction="default"))]
+        fn orient<'py>(
+            slf: PyRef<'py, Self>,
+            py: Python<'py>,
+            direction: &str,
+        ) -> PyResult<Py<PyAny>> {
+            let multipolygon = slf.multipolygon.orient(parse_direction(direction)?);
+            shape_to_py(
+                py,
+                Shapes::MultiPolygon(Arc::new(multipolygon)),
+                slf.as_super().srid,
+            )
         }
     }
 
     #[pymethods]
     impl Shape {
-        fn distance(&self, rhs: &Shape) -> f64 {
-            match (&self.inner, &rhs.inner) {
+        #[pyo3(signature = (rhs, metric="euclidean"))]
+        fn distance(&self, rhs: &Shape, metric: &str) -> PyResult<f64> {
+            common_srid(self.srid, rhs.srid)?;
+            match parse_metric(metric)? {
+                Metric::Euclidean => {}
+                Metric::Haversine => {
+                    return Ok(metric_distance(&self.inner, &rhs.inner, &Haversine));
+                }
+                Metric::Geodesic => return Ok(metric_distance(&self.inner, &rhs.inner, &Geodesic)),
+                Metric::Rhumb => return Ok(metric_distance(&self.inner, &rhs.inner, &Rhumb)),
+            }
+            let result = match (&self.inner, &rhs.inner) {
                 (Shapes::Point(p), Shapes::Point(q)) => Euclidean.distance(p.as_ref(), q.as_ref()),
                 (Shapes::LineString(p), Shapes::Point(q)) => {
                     Euclidean.distance(p.as_ref(), q.as_ref())
@@ -564,11 +2709,29 @@ mod rust_geo_python {
                 (Shapes::MultiPolygon(p), Shapes::MultiPoint(q)) => {
                     Euclidean.distance(p.as_ref(), q.as_ref())
                 }
-            }
+            };
+            Ok(result)
+        }
+
+        #[pyo3(signature = (rhs, metric="euclidean"))]
+        fn frechet_distance(&self, rhs: &Shape, metric: &str) -> PyResult<f64> {
+            common_srid(self.srid, rhs.srid)?;
+            let lhs = shape_linestring(&self.inner, "frechet_distance")?;
+            let rhs = shape_linestring(&rhs.inner, "frechet_distance")?;
+            Ok(frechet_distance_metric(&lhs, &rhs, parse_metric(metric)?))
+        }
+
+        #[pyo3(signature = (rhs, metric="euclidean"))]
+        fn dtw_distance(&self, rhs: &Shape, metric: &str) -> PyResult<f64> {
+            common_srid(self.srid, rhs.srid)?;
+            let lhs = shape_linestring(&self.inner, "dtw_distance")?;
+            let rhs = shape_linestring(&rhs.inner, "dtw_distance")?;
+            Ok(dtw_distance_metric(&lhs, &rhs, parse_metric(metric)?))
         }
 
-        fn hausdorff_distance(&self, rhs: &Shape) -> f64 {
-            match (&self.inner, &rhs.inner) {
+        fn hausdorff_distance(&self, rhs: &Shape) -> PyResult<f64> {
+            common_srid(self.srid, rhs.srid)?;
+            let result = match (&self.inner, &rhs.inner) {
                 (Shapes::Point(p), Shapes::Point(q)) => p.as_ref().hausdorff_distance(q.as_ref()),
                 (Shapes::LineString(p), Shapes::Point(q)) => {
                     p.as_ref().hausdorff_distance(q.as_ref())
@@ -671,11 +2834,103 @@ mod rust_geo_python {
                 (Shapes::MultiPolygon(p), Shapes::MultiPoint(q)) => {
                     p.as_ref().hausdorff_distance(q.as_ref())
                 }
+            };
+            Ok(result)
+        }
+
+        fn closest_point<'py>(
+            &self,
+            py: Python<'py>,
+            point: PointLike<'py>,
+        ) -> PyResult<(Py<PyAny>, &'static str)> {
+            let point = match point {
+                PointLike::Point(p) => {
+                    common_srid(self.srid, p.as_super().srid)?;
+                    *p.point
+                }
+                PointLike::Coord((x, y)) => Point::new(x, y),
+            };
+            let closest = match &self.inner {
+                Shapes::Point(p) => p.closest_point(&point),
+                Shapes::MultiPoint(p) => p.closest_point(&point),
+                Shapes::LineString(p) => p.closest_point(&point),
+                Shapes::MultiLineString(p) => p.closest_point(&point),
+                Shapes::Polygon(p) => p.closest_point(&point),
+                Shapes::MultiPolygon(p) => p.closest_point(&point),
+            };
+            match closest {
+                Closest::Intersection(p) => Ok((
+                    shape_to_py(py, Shapes::Point(Arc::new(p)), self.srid)?,
+                    "intersection",
+                )),
+                Closest::SinglePoint(p) => Ok((
+                    shape_to_py(py, Shapes::Point(Arc::new(p)), self.srid)?,
+                    "single",
+                )),
+                Closest::Indeterminate => Ok((py.None(), "indeterminate")),
             }
         }
 
-        fn contains(&self, rhs: &Shape) -> bool {
-            match (&self.inner, &rhs.inner) {
+        fn shortest_line<'py>(&self, py: Python<'py>, rhs: &Shape) -> PyResult<Py<PyAny>> {
+            let srid = common_srid(self.srid, rhs.srid)?;
+            match shortest_line(&self.inner, &rhs.inner) {
+                Some(line) => shape_to_py(py, Shapes::LineString(Arc::new(line.into())), srid),
+                None => Ok(py.None()),
+            }
+        }
+
+        #[pyo3(signature = (tolerance=0.0))]
+        fn remove_repeated_points<'py>(
+            &self,
+            py: Python<'py>,
+            tolerance: f64,
+        ) -> PyResult<Py<PyAny>> {
+            if tolerance.is_nan() || tolerance < 0.0 {
+                return Err(PyValueError::new_err("tolerance must be zero or positive"));
+            }
+            let shape = remove_repeated_points(&self.inner, tolerance);
+            shape_to_py(py, shape, self.srid)
+        }
+
+        fn snap_to_grid<'py>(&self, py: Python<'py>, size: f64) -> PyResult<Py<PyAny>> {
+            check_grid_size(size)?;
+            shape_to_py(py, snap_shape(&self.inner, size), self.srid)
+        }
+
+        fn set_precision<'py>(&self, py: Python<'py>, grid_size: f64) -> PyResult<Py<PyAny>> {
+            check_grid_size(grid_size)?;
+            shape_to_py(py, set_precision_shape(&self.inner, grid_size), self.srid)
+        }
+
+        #[pyo3(signature = (keep_collapsed=false))]
+        fn make_valid<'py>(&self, py: Python<'py>, keep_collapsed: bool) -> PyResult<Py<PyAny>> {
+            let polygons = shape_polygons(&self.inner, "make_valid")?;
+            let repaired = make_valid_polygons(&polygons);
+            // The collapsed linework comes back as a second element only when asked for, so the
+            // result type follows the argument and never the input
+            let collapsed = keep_collapsed.then(|| collapsed_linework(&polygons, &repaired));
+            let repaired = shape_to_py(py, Shapes::MultiPolygon(Arc::new(repaired)), self.srid)?;
+            match collapsed {
+                None => Ok(repaired),
+                Some(collapsed) => (
+                    repaired,
+                    shape_to_py(py, Shapes::MultiLineString(Arc::new(collapsed)), self.srid)?,
+                )
+                    .into_py_any(py),
+            }
+        }
+
+        fn is_valid(&self) -> bool {
+            shape_validation_errors(&self.inner).is_empty()
+        }
+
+        fn explain_validity(&self) -> Vec<String> {
+            shape_validation_errors(&self.inner)
+        }
+
+        fn contains(&self, rhs: &Shape) -> PyResult<bool> {
+            common_srid(self.srid, rhs.srid)?;
+            let result = match (&self.inner, &rhs.inner) {
                 (Shapes::Point(p), Shapes::Point(q)) => p.as_ref().contains(q.as_ref()),
                 (Shapes::LineString(p), Shapes::Point(q)) => p.as_ref().contains(q.as_ref()),
                 (Shapes::Point(p), Shapes::LineString(q)) => p.as_ref().contains(q.as_ref()),
@@ -728,11 +2983,13 @@ mod rust_geo_python {
                 }
                 (Shapes::Polygon(p), Shapes::MultiPoint(q)) => p.as_ref().contains(q.as_ref()),
                 (Shapes::MultiPolygon(p), Shapes::MultiPoint(q)) => p.as_ref().contains(q.as_ref()),
-            }
+            };
+            Ok(result)
         }
 
-        fn contains_properly(&self, rhs: &Shape) -> bool {
-            match (&self.inner, &rhs.inner) {
+        fn contains_properly(&self, rhs: &Shape) -> PyResult<bool> {
+            common_srid(self.srid, rhs.srid)?;
+            let result = match (&self.inner, &rhs.inner) {
                 (Shapes::Point(p), Shapes::Point(q)) => p.as_ref().contains_properly(q.as_ref()),
                 (Shapes::LineString(p), Shapes::Point(q)) => {
                     p.as_ref().contains_properly(q.as_ref())
@@ -835,9 +3092,30 @@ mod rust_geo_python {
                 (Shapes::MultiPolygon(p), Shapes::MultiPoint(q)) => {
                     p.as_ref().contains_properly(q.as_ref())
                 }
+            };
+            Ok(result)
+        }
+
+        #[getter]
+        fn srid(&self) -> Option<i32> {
+            self.srid
+        }
+
+        fn with_srid<'py>(&self, py: Python<'py>, srid: Option<i32>) -> PyResult<Py<PyAny>> {
+            shape_to_py(py, self.inner.clone(), srid)
+        }
+
+        fn to_ewkt(&self) -> String {
+            match self.srid {
+                Some(srid) => format!("SRID={srid};{}", self.to_wkt()),
+                None => self.to_wkt(),
             }
         }
 
+        fn to_ewkb(&self) -> PyResult<Vec<u8>> {
+            shape_to_ewkb(&self.inner, self.srid)
+        }
+
         fn to_wkt(&self) -> String {
             match &self.inner {
                 Shapes::Point(p) => p.as_ref().wkt_string(),
@@ -849,28 +3127,397 @@ mod rust_geo_python {
             }
         }
 
-        fn buffer<'py>(&self, py: Python<'py>, radius: f64) -> PyResult<Py<PyAny>> {
-            let polygons = match &self.inner {
-                Shapes::Point(p) => p.buffer(radius),
-                Shapes::MultiPoint(p) => p.buffer(radius),
-                Shapes::LineString(p) => p.buffer(radius),
-                Shapes::MultiLineString(p) => p.buffer(radius),
-                Shapes::MultiPolygon(p) => p.buffer(radius),
-                Shapes::Polygon(p) => p.buffer(radius),
+        #[pyo3(signature = (
+            radius,
+            cap_style="round",
+            join_style="round",
+            mitre_limit=5.0,
+            quad_segs=None,
+            single_sided=false
+        ))]
+        #[allow(clippy::too_many_arguments)]
+        fn buffer<'py>(
+            &self,
+            py: Python<'py>,
+            radius: f64,
+            cap_style: &str,
+            join_style: &str,
+            mitre_limit: f64,
+            quad_segs: Option<u32>,
+            single_sided: bool,
+        ) -> PyResult<Py<PyAny>> {
+            let options =
+                BufferOptions::new(cap_style, join_style, mitre_limit, quad_segs, single_sided)?;
+            let polygons = buffer_shape(&self.inner, radius, &options);
+            shape_to_py(py, Shapes::MultiPolygon(Arc::new(polygons)), self.srid)
+        }
+
+        #[pyo3(signature = (iterations=1))]
+        fn smooth<'py>(&self, py: Python<'py>, iterations: usize) -> PyResult<Py<PyAny>> {
+            // Every pass doubles the vertex count, so a few passes are all that is ever useful
+            if iterations > 10 {
+                return Err(PyValueError::new_err("iterations must be at most 10"));
+            }
+            let smoothed = match &self.inner {
+                Shapes::LineString(p) => {
+                    Shapes::LineString(Arc::new(p.chaikin_smoothing(iterations)))
+                }
+                Shapes::MultiLineString(p) => {
+                    Shapes::MultiLineString(Arc::new(p.chaikin_smoothing(iterations)))
+                }
+                Shapes::Polygon(p) => Shapes::Polygon(Arc::new(p.chaikin_smoothing(iterations))),
+                Shapes::MultiPolygon(p) => {
+                    Shapes::MultiPolygon(Arc::new(p.chaikin_smoothing(iterations)))
+                }
+                Shapes::Point(_) | Shapes::MultiPoint(_) => {
+                    return Err(PyValueError::new_err(
+                        "smooth expects line or polygon shapes",
+                    ));
+                }
             };
-            let multipolygon_arc = Arc::new(polygons);
-            let initializer: PyClassInitializer<RustMultiPolygon> = PyClassInitializer::from((
-                RustMultiPolygon {
-                    multipolygon: multipolygon_arc.clone(),
-                },
-                Shape {
-                    inner: Shapes::MultiPolygon(multipolygon_arc),
+            shape_to_py(py, smoothed, self.srid)
+        }
+
+        #[pyo3(signature = (max_segment_length, metric="euclidean"))]
+        fn densify<'py>(
+            &self,
+            py: Python<'py>,
+            max_segment_length: f64,
+            metric: &str,
+        ) -> PyResult<Py<PyAny>> {
+            if max_segment_length.is_nan() || max_segment_length <= 0.0 {
+                return Err(PyValueError::new_err(
+                    "max_segment_length must be greater than zero",
+                ));
+            }
+            let densified = match parse_metric(metric)? {
+                Metric::Euclidean => densify_shape(&self.inner, &Euclidean, max_segment_length),
+                Metric::Haversine => densify_shape(&self.inner, &Haversine, max_segment_length),
+                Metric::Geodesic => densify_shape(&self.inner, &Geodesic, max_segment_length),
+                Metric::Rhumb => densify_shape(&self.inner, &Rhumb, max_segment_length),
+            };
+            shape_to_py(py, densified, self.srid)
+        }
+
+        #[pyo3(signature = (n, metric="euclidean"))]
+        fn segmentize<'py>(&self, py: Python<'py>, n: usize, metric: &str) -> PyResult<Py<PyAny>> {
+            if n == 0 {
+                return Err(PyValueError::new_err("n must be at least 1"));
+            }
+            let metric = parse_metric(metric)?;
+            let segments = shape_linework(&self.inner)?
+                .into_iter()
+                .map(|ls| match metric {
+                    // geo cannot split degenerate lines, so they are kept whole
+                    _ if Euclidean.length(ls) == 0.0 => Ok(Some(ls.clone().into())),
+                    Metric::Euclidean => Ok(ls.line_segmentize(n)),
+                    Metric::Haversine => Ok(ls.line_segmentize_haversine(n)),
+                    _ => Err(PyValueError::new_err(
+                        "segmentize supports the 'euclidean' and 'haversine' metrics",
+                    )),
+                })
+                .collect::<PyResult<Vec<Option<MultiLineString>>>>()?
+                .into_iter()
+                .flatten()
+                .flatten()
+                .collect::<MultiLineString>();
+            shape_to_py(py, Shapes::MultiLineString(Arc::new(segments)), self.srid)
+        }
+
+        #[pyo3(signature = (metric="euclidean"))]
+        fn length(&self, metric: &str) -> PyResult<f64> {
+            let length = match parse_metric(metric)? {
+                Metric::Euclidean => shape_length(&self.inner, &Euclidean),
+                Metric::Haversine => shape_length(&self.inner, &Haversine),
+                Metric::Geodesic => shape_length(&self.inner, &Geodesic),
+                Metric::Rhumb => shape_length(&self.inner, &Rhumb),
+            };
+            Ok(length)
+        }
+
+        fn area(&self) -> f64 {
+            match &self.inner {
+                Shapes::Point(p) => p.signed_area(),
+                Shapes::MultiPoint(p) => p.signed_area(),
+                Shapes::LineString(p) => p.signed_area(),
+                Shapes::MultiLineString(p) => p.signed_area(),
+                Shapes::MultiPolygon(p) => p.signed_area(),
+                Shapes::Polygon(p) => p.signed_area(),
+            }
+        }
+
+        fn unsigned_area(&self) -> f64 {
+            match &self.inner {
+                Shapes::Point(p) => p.unsigned_area(),
+                Shapes::MultiPoint(p) => p.unsigned_area(),
+                Shapes::LineString(p) => p.unsigned_area(),
+                Shapes::MultiLineString(p) => p.unsigned_area(),
+                Shapes::MultiPolygon(p) => p.unsigned_area(),
+                Shapes::Polygon(p) => p.unsigned_area(),
+            }
+        }
+
+        fn geodesic_area(&self) -> f64 {
+            match &self.inner {
+                Shapes::Point(p) => p.geodesic_area_unsigned(),
+                Shapes::MultiPoint(p) => p.geodesic_area_unsigned(),
+                Shapes::LineString(p) => p.geodesic_area_unsigned(),
+                Shapes::MultiLineString(p) => p.geodesic_area_unsigned(),
+                Shapes::MultiPolygon(p) => p.geodesic_area_unsigned(),
+                Shapes::Polygon(p) => p.geodesic_area_unsigned(),
+            }
+        }
+
+        fn centroid<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            let centroid = match &self.inner {
+                Shapes::Point(p) => Some(p.centroid()),
+                Shapes::MultiPoint(p) => p.centroid(),
+                Shapes::LineString(p) => p.centroid(),
+                Shapes::MultiLineString(p) => p.centroid(),
+                Shapes::MultiPolygon(p) => p.centroid(),
+                Shapes::Polygon(p) => p.centroid(),
+            };
+            match centroid {
+                Some(p) => shape_to_py(py, Shapes::Point(Arc::new(p)), self.srid),
+                None => Ok(py.None()),
+            }
+        }
+
+        fn interior_point<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            let interior_point = match &self.inner {
+                Shapes::Point(p) => Some(p.interior_point()),
+                Shapes::MultiPoint(p) => p.interior_point(),
+                Shapes::LineString(p) => p.interior_point(),
+                Shapes::MultiLineString(p) => p.interior_point(),
+                Shapes::MultiPolygon(p) => p.interior_point(),
+                Shapes::Polygon(p) => p.interior_point(),
+            };
+            match interior_point {
+                Some(p) => shape_to_py(py, Shapes::Point(Arc::new(p)), self.srid),
+                None => Ok(py.None()),
+            }
+        }
+
+        #[getter]
+        fn bounds(&self) -> (f64, f64, f64, f64) {
+            rect_to_bounds(shape_bounding_rect(&self.inner))
+        }
+
+        fn envelope<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            match shape_bounding_rect(&self.inner) {
+                Some(r) => shape_to_py(py, Shapes::Polygon(Arc::new(r.to_polygon())), self.srid),
+                None => Ok(py.None()),
+            }
+        }
+
+        fn convex_hull<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            let hull = match &self.inner {
+                Shapes::Point(p) => p.convex_hull(),
+                Shapes::MultiPoint(p) => p.convex_hull(),
+                Shapes::LineString(p) => p.convex_hull(),
+                Shapes::MultiLineString(p) => p.convex_hull(),
+                Shapes::MultiPolygon(p) => p.convex_hull(),
+                Shapes::Polygon(p) => p.convex_hull(),
+            };
+            shape_to_py(py, Shapes::Polygon(Arc::new(hull)), self.srid)
+        }
+
+        #[pyo3(signature = (concavity=2.0, length_threshold=0.0))]
+        fn concave_hull<'py>(
+            &self,
+            py: Python<'py>,
+            concavity: f64,
+            length_threshold: f64,
+        ) -> PyResult<Py<PyAny>> {
+            let options = ConcaveHullOptions::default()
+                .concavity(concavity)
+                .length_threshold(length_threshold);
+            let hull = match &self.inner {
+                Shapes::Point(p) => {
+                    MultiPoint::from(vec![*p.as_ref()]).concave_hull_with_options(options)
+                }
+                Shapes::MultiPoint(p) => p.concave_hull_with_options(options),
+                Shapes::LineString(p) => p.concave_hull_with_options(options),
+                Shapes::MultiLineString(p) => p.concave_hull_with_options(options),
+                Shapes::MultiPolygon(p) => p.concave_hull_with_options(options),
+                Shapes::Polygon(p) => p.concave_hull_with_options(options),
+            };
+            shape_to_py(py, Shapes::Polygon(Arc::new(hull)), self.srid)
+        }
+
+        fn minimum_rotated_rect<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            let rect = match &self.inner {
+                Shapes::Point(p) => p.minimum_rotated_rect(),
+                Shapes::MultiPoint(p) => p.minimum_rotated_rect(),
+                Shapes::LineString(p) => p.minimum_rotated_rect(),
+                Shapes::MultiLineString(p) => p.minimum_rotated_rect(),
+                Shapes::MultiPolygon(p) => p.minimum_rotated_rect(),
+                Shapes::Polygon(p) => p.minimum_rotated_rect(),
+            };
+            match rect {
+                Some(r) => shape_to_py(py, Shapes::Polygon(Arc::new(r)), self.srid),
+                None => Ok(py.None()),
+            }
+        }
+
+        fn minimum_bounding_circle<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            let hull = match &self.inner {
+                Shapes::Point(p) => p.convex_hull(),
+                Shapes::MultiPoint(p) => p.convex_hull(),
+                Shapes::LineString(p) => p.convex_hull(),
+                Shapes::MultiLineString(p) => p.convex_hull(),
+                Shapes::MultiPolygon(p) => p.convex_hull(),
+                Shapes::Polygon(p) => p.convex_hull(),
+            };
+            match minimum_bounding_circle(&hull.exterior().0) {
+                Some((center, radius)) if radius > 0.0 => {
+                    let circle = circle_polygon(center, radius);
+                    shape_to_py(py, Shapes::Polygon(Arc::new(circle)), self.srid)
+                }
+                Some((center, _)) => {
+                    shape_to_py(py, Shapes::Point(Arc::new(Point::from(center))), self.srid)
+                }
+                None => Ok(py.None()),
+            }
+        }
+
+        fn translate<'py>(&self, py: Python<'py>, dx: f64, dy: f64) -> PyResult<Py<PyAny>> {
+            let transform = AffineTransform::translate(dx, dy);
+            shape_to_py(py, transform_shape(&self.inner, &transform), self.srid)
+        }
+
+        #[pyo3(signature = (angle, origin=None))]
+        fn rotate<'py>(
+            &self,
+            py: Python<'py>,
+            angle: f64,
+            origin: Option<Origin>,
+        ) -> PyResult<Py<PyAny>> {
+            let transform = AffineTransform::rotate(angle, origin_coord(&self.inner, origin)?);
+            shape_to_py(py, transform_shape(&self.inner, &transform), self.srid)
+        }
+
+        #[pyo3(signature = (sx, sy, origin=None))]
+        fn scale<'py>(
+            &self,
+            py: Python<'py>,
+            sx: f64,
+            sy: f64,
+            origin: Option<Origin>,
+        ) -> PyResult<Py<PyAny>> {
+            let transform = AffineTransform::scale(sx, sy, origin_coord(&self.inner, origin)?);
+            shape_to_py(py, transform_shape(&self.inner, &transform), self.srid)
+        }
+
+        #[pyo3(signature = (xs, ys, origin=None))]
+        fn skew<'py>(
+            &self,
+            py: Python<'py>,
+            xs: f64,
+            ys: f64,
+            origin: Option<Origin>,
+        ) -> PyResult<Py<PyAny>> {
+            let transform = AffineTransform::skew(xs, ys, origin_coord(&self.inner, origin)?);
+            shape_to_py(py, transform_shape(&self.inner, &transform), self.srid)
+        }
+
+        fn affine_transform<'py>(
+            &self,
+            py: Python<'py>,
+            matrix: PyReadonlyArray2<'py, f64>,
+        ) -> PyResult<Py<PyAny>> {
+            let shape = matrix.shape();
+            if !(shape[0] == 2 || shape[0] == 3) || shape[1] != 3 {
+                return Err(PyValueError::new_err(format!(
+                    "Expected a (2, 3) or (3, 3) matrix, got {shape:?}"
+                )));
+            }
+            let m = matrix.as_array();
+            let transform = AffineTransform::new(
+                m[[0, 0]],
+                m[[0, 1]],
+                m[[0, 2]],
+                m[[1, 0]],
+                m[[1, 1]],
+                m[[1, 2]],
+            );
+            shape_to_py(py, transform_shape(&self.inner, &transform), self.srid)
+        }
+
+        fn map_coords<'py>(&self, py: Python<'py>, func: Bound<'py, PyAny>) -> PyResult<Py<PyAny>> {
+            let coords = shape_coords(&self.inner);
+            let mut arr = Array2::zeros((coords.len(), 2));
+            coords.iter().enumerate().for_each(|(i, c)| {
+                arr[[i, 0]] = c.x;
+                arr[[i, 1]] = c.y;
+            });
+            let result = func.call1((PyArray2::from_owned_array(py, arr),))?;
+            let mapped = result.extract::<PyReadonlyArray2<f64>>()?;
+            if mapped.shape() != [coords.len(), 2] {
+                return Err(PyValueError::new_err(format!(
+                    "map_coords function must return an array of shape ({}, 2), got {:?}",
+                    coords.len(),
+                    mapped.shape()
+                )));
+            }
+            let mapped_coords = mapped
+                .as_array()
+                .axis_iter(Axis(0))
+                .map(|x| Coord { x: x[0], y: x[1] })
+                .collect::<Vec<Coord>>();
+            shape_to_py(py, rebuild_shape(&self.inner, mapped_coords), self.srid)
+        }
+
+        #[pyo3(signature = (src=None, dst=None, *, srid=None))]
+        fn to_crs<'py>(
+            &self,
+            py: Python<'py>,
+            src: Option<Crs>,
+            dst: Option<Crs>,
+            srid: Option<i32>,
+        ) -> PyResult<Py<PyAny>> {
+            let dst = dst.ok_or_else(|| PyValueError::new_err("to_crs needs a dst CRS"))?;
+            let src = match (src, self.srid) {
+                (Some(src), Some(own)) => match crs_srid(&src) {
+                    Some(src_srid) if src_srid != own => {
+                        return Err(PyValueError::new_err(format!(
+                            "src CRS EPSG:{src_srid} contradicts the shape srid {own}"
+                        )));
+                    }
+                    _ => src,
                 },
-            ));
-            Ok(Py::new(py, initializer)?.into_any())
+                (Some(src), None) => src,
+                (None, Some(own)) => Crs::Name(format!("EPSG:{own}")),
+                (None, None) => {
+                    return Err(PyValueError::new_err(
+                        "Shape has no srid, pass the src CRS explicitly",
+                    ));
+                }
+            };
+            // The output keeps an srid whenever the input had one, a dst that cannot be mapped
+            // to an EPSG code needs the srid spelled out
+            let srid = match (srid, crs_srid(&dst)) {
+                (Some(srid), Some(dst_srid)) if srid != dst_srid => {
+                    return Err(PyValueError::new_err(format!(
+                        "srid {srid} contradicts the dst CRS EPSG:{dst_srid}"
+                    )));
+                }
+                (Some(srid), _) | (None, Some(srid)) => Some(srid),
+                (None, None) if self.srid.is_some() => {
+                    return Err(PyValueError::new_err(
+                        "Cannot derive an srid from the dst CRS, pass srid explicitly",
+                    ));
+                }
+                (None, None) => None,
+            };
+            let (src, dst) = (parse_crs(&src)?, parse_crs(&dst)?);
+            let mut coords = shape_coords(&self.inner);
+            reproject_coords(&mut coords, &src, &dst)?;
+            shape_to_py(py, rebuild_shape(&self.inner, coords), srid)
         }
 
         fn boundary<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+            let srid = self.srid;
             match &self.inner {
                 Shapes::Point(_) => Ok(py.None()),
                 Shapes::MultiPoint(_) => Ok(py.None()),
@@ -884,6 +3531,7 @@ mod rust_geo_python {
                             },
                             Shape {
                                 inner: Shapes::MultiPoint(multipoint_arc),
+                                srid,
                             },
                         ));
                     Ok(Py::new(py, initializer)?.into_any())
@@ -904,6 +3552,7 @@ mod rust_geo_python {
                             },
                             Shape {
                                 inner: Shapes::MultiPoint(multipoint_arc),
+                                srid,
                             },
                         ));
                     Ok(Py::new(py, initializer)?.into_any())
@@ -925,6 +3574,7 @@ mod rust_geo_python {
                             },
                             Shape {
                                 inner: Shapes::MultiLineString(multilinestring_arc),
+                                srid,
                             },
                         ));
                     Ok(Py::new(py, initializer)?.into_any())
@@ -945,6 +3595,7 @@ mod rust_geo_python {
                             },
                             Shape {
                                 inner: Shapes::MultiLineString(multilinestring_arc),
+                                srid,
                             },
                         ));
                     Ok(Py::new(py, initializer)?.into_any())
@@ -954,12 +3605,28 @@ mod rust_geo_python {
     }
 
     #[pyfunction]
-    fn union<'py>(py: Python<'py>, rust_polygons: Vec<RustPolygon>) -> PyResult<Py<PyAny>> {
+    #[pyo3(signature = (rust_polygons, grid_size=None))]
+    fn union<'py>(
+        py: Python<'py>,
+        rust_polygons: Vec<PyRef<'py, RustPolygon>>,
+        grid_size: Option<f64>,
+    ) -> PyResult<Py<PyAny>> {
+        let srid = rust_polygons
+            .iter()
+            .try_fold(None, |srid, x| common_srid(srid, x.as_super().srid))?;
         let polygons = rust_polygons
             .iter()
             .map(|x| x.polygon.as_ref())
             .collect::<Vec<&Polygon>>();
-        let union = unary_union(polygons);
+        let union = match grid_size {
+            Some(grid_size) => {
+                check_grid_size(grid_size)?;
+                let polygons = polygons.into_iter().cloned().collect::<Vec<Polygon>>();
+                let polygons = set_precision_polygons(&polygons, grid_size);
+                set_precision_polygons(&unary_union(&polygons).0, grid_size)
+            }
+            None => unary_union(polygons),
+        };
         let multipolygon_arc = Arc::new(union);
         let initializer: PyClassInitializer<RustMultiPolygon> = PyClassInitializer::from((
             RustMultiPolygon {
@@ -967,34 +3634,372 @@ mod rust_geo_python {
             },
             Shape {
                 inner: Shapes::MultiPolygon(multipolygon_arc),
+                srid,
             },
         ));
         Ok(Py::new(py, initializer)?.into_any())
     }
 
+    #[derive(FromPyObject)]
+    enum Radii<'py> {
+        Array(PyReadonlyArray1<'py, f64>),
+        Scalar(f64),
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (
+        shapes,
+        radii,
+        union=false,
+        cap_style="round",
+        join_style="round",
+        mitre_limit=5.0,
+        quad_segs=None,
+        single_sided=false
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn buffer_many<'py>(
+        py: Python<'py>,
+        shapes: Vec<Shape>,
+        radii: Radii<'py>,
+        union: bool,
+        cap_style: &str,
+        join_style: &str,
+        mitre_limit: f64,
+        quad_segs: Option<u32>,
+        single_sided: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let options =
+            BufferOptions::new(cap_style, join_style, mitre_limit, quad_segs, single_sided)?;
+        let radii = match radii {
+            Radii::Array(x) => {
+                if x.len() != shapes.len() {
+                    return Err(PyValueError::new_err(format!(
+                        "Expected {} radii, got {}",
+                        shapes.len(),
+                        x.len()
+                    )));
+                }
+                x.as_array().to_vec()
+            }
+            Radii::Scalar(x) => vec![x; shapes.len()],
+        };
+        let srids = shapes.iter().map(|shape| shape.srid).collect::<Vec<_>>();
+        let inners = shapes
+            .into_iter()
+            .map(|shape| shape.inner)
+            .collect::<Vec<Shapes>>();
+        let buffers = py.detach(|| {
+            inners
+                .into_par_iter()
+                .zip(radii)
+                .map(|(shape, radius)| buffer_shape(&shape, radius, &options))
+                .collect::<Vec<MultiPolygon>>()
+        });
+        if union {
+            let srid = srids.into_iter().try_fold(None, common_srid)?;
+            let union = py.detach(|| {
+                let polygons = buffers.iter().flatten().collect::<Vec<&Polygon>>();
+                unary_union(polygons)
+            });
+            return shape_to_py(py, Shapes::MultiPolygon(Arc::new(union)), srid);
+        }
+        buffers
+            .into_iter()
+            .zip(srids)
+            .map(|(x, srid)| shape_to_py(py, Shapes::MultiPolygon(Arc::new(x)), srid))
+            .collect::<PyResult<Vec<Py<PyAny>>>>()?
+            .into_py_any(py)
+    }
+
+    fn linestring_distances_many<'py>(
+        py: Python<'py>,
+        name: &str,
+        trace: Shape,
+        others: Vec<Shape>,
+        distance: impl Fn(&LineString, &LineString) -> f64 + Sync,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        others
+            .iter()
+            .try_for_each(|x| common_srid(trace.srid, x.srid).map(|_| ()))?;
+        let trace = shape_linestring(&trace.inner, name)?;
+        let others = others
+            .iter()
+            .map(|x| shape_linestring(&x.inner, name))
+            .collect::<PyResult<Vec<Arc<LineString>>>>()?;
+        let distances = py.detach(|| {
+            others
+                .into_par_iter()
+                .map(|x| distance(&trace, &x))
+                .collect::<Vec<f64>>()
+        });
+        Ok(distances.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (trace, others, metric="euclidean"))]
+    fn frechet_distance_many<'py>(
+        py: Python<'py>,
+        trace: Shape,
+        others: Vec<Shape>,
+        metric: &str,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let metric = parse_metric(metric)?;
+        linestring_distances_many(py, "frechet_distance_many", trace, others, |a, b| {
+            frechet_distance_metric(a, b, metric)
+        })
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (trace, others, metric="euclidean"))]
+    fn dtw_distance_many<'py>(
+        py: Python<'py>,
+        trace: Shape,
+        others: Vec<Shape>,
+        metric: &str,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let metric = parse_metric(metric)?;
+        linestring_distances_many(py, "dtw_distance_many", trace, others, |a, b| {
+            dtw_distance_metric(a, b, metric)
+        })
+    }
+
+    #[pyfunction]
+    fn simplify_coverage<'py>(
+        py: Python<'py>,
+        shapes: Vec<Shape>,
+        epsilon: f64,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        shapes
+            .iter()
+            .try_fold(None, |srid, x| common_srid(srid, x.srid))?;
+        let polygons = shapes
+            .iter()
+            .map(|shape| match &shape.inner {
+                Shapes::Polygon(p) => Ok(vec![p.as_ref().clone()]),
+                Shapes::MultiPolygon(p) => Ok(p.0.clone()),
+                _ => Err(PyValueError::new_err(
+                    "simplify_coverage expects RustPolygon or RustMultiPolygon shapes",
+                )),
+            })
+            .collect::<PyResult<Vec<Vec<Polygon>>>>()?;
+        let neighbours = coverage_neighbours(&polygons.concat());
+        let mut arcs: HashMap<Vec<CoordKey>, Vec<Coord>> = HashMap::new();
+        shapes
+            .iter()
+            .zip(polygons)
+            .map(|(shape, polygons)| {
+                let simple_polygons = polygons
+                    .iter()
+                    .filter_map(|p| simplify_coverage_polygon(p, epsilon, &neighbours, &mut arcs))
+                    .collect::<Vec<Polygon>>();
+                match &shape.inner {
+                    Shapes::Polygon(_) => {
+                        let polygon = simple_polygons
+                            .into_iter()
+                            .next()
+                            .unwrap_or_else(|| Polygon::new(LineString::new(vec![]), vec![]));
+                        shape_to_py(py, Shapes::Polygon(Arc::new(polygon)), shape.srid)
+                    }
+                    _ => shape_to_py(
+                        py,
+                        Shapes::MultiPolygon(Arc::new(MultiPolygon::new(simple_polygons))),
+                        shape.srid,
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    #[pyfunction]
+    fn to_crs_array<'py>(
+        py: Python<'py>,
+        xy: PyReadonlyArray2<'py, f64>,
+        src: Crs,
+        dst: Crs,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        if xy.shape()[1] != 2 {
+            return Err(PyValueError::new_err(format!(
+                "xy must be an array of shape (N, 2), got {:?}",
+                xy.shape()
+            )));
+        }
+        let (src, dst) = (parse_crs(&src)?, parse_crs(&dst)?);
+        let mut coords = array2_to_linestring(&xy).0;
+        reproject_coords(&mut coords, &src, &dst)?;
+        Ok(linestring_to_pyarray2(py, &LineString::new(coords)))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (shape_or_points, indices=false))]
+    fn delaunay_triangles<'py>(
+        py: Python<'py>,
+        shape_or_points: ShapeOrPoints<'py>,
+        indices: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let (coords, srid) = match shape_or_points {
+            ShapeOrPoints::Shape(shape) => (shape_coords(&shape.inner), shape.srid),
+            ShapeOrPoints::Points(xy) => (array2_to_linestring(&xy).0, None),
+        };
+        // only the vertices matter for an unconstrained triangulation
+        let triangles = LineString::new(coords.clone())
+            .unconstrained_triangulation()
+            .map_err(|e| PyValueError::new_err(format!("Triangulation failed: {e}")))?;
+        triangles_to_py(py, triangles, &coords, indices, srid)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (multipoint, extent=None))]
+    fn voronoi_polygons<'py>(
+        py: Python<'py>,
+        multipoint: PyRef<'py, RustMultiPoint>,
+        extent: Option<Shape>,
+    ) -> PyResult<Py<PyAny>> {
+        let srid = common_srid(
+            multipoint.as_super().srid,
+            extent.as_ref().and_then(|x| x.srid),
+        )?;
+        let extent = match extent.map(|x| x.inner) {
+            None => None,
+            Some(Shapes::Polygon(p)) => Some(MultiPolygon::new(vec![p.as_ref().clone()])),
+            Some(Shapes::MultiPolygon(p)) => Some(p.as_ref().clone()),
+            Some(_) => {
+                return Err(PyValueError::new_err(
+                    "extent must be a RustPolygon or RustMultiPolygon",
+                ));
+            }
+        };
+        let sites = multipoint
+            .multipoint
+            .iter()
+            .map(|p| p.0)
+            .collect::<Vec<Coord>>();
+        let bounds = extent
+            .iter()
+            .filter_map(|e| e.bounding_rect())
+            .chain(multipoint.multipoint.bounding_rect())
+            .reduce(|a, b| {
+                Rect::new(
+                    Coord {
+                        x: a.min().x.min(b.min().x),
+                        y: a.min().y.min(b.min().y),
+                    },
+                    Coord {
+                        x: a.max().x.max(b.max().x),
+                        y: a.max().y.max(b.max().y),
+                    },
+                )
+            });
+        let Some(bounds) = bounds else {
+            return shape_to_py(
+                py,
+                Shapes::MultiPolygon(Arc::new(MultiPolygon::new(vec![]))),
+                srid,
+            );
+        };
+        let size = bounds.width().max(bounds.height());
+        let margin = if size > 0.0 { size } else { 1.0 };
+        let frame = Rect::new(
+            bounds.min()
+                - Coord {
+                    x: margin,
+                    y: margin,
+                },
+            bounds.max()
+                + Coord {
+                    x: margin,
+                    y: margin,
+                },
+        );
+        let cells = voronoi_cells(&sites, frame)?;
+        match extent {
+            None => shape_to_py(
+                py,
+                Shapes::MultiPolygon(Arc::new(MultiPolygon::new(cells))),
+                srid,
+            ),
+            // A clipped cell can fall apart into several pieces, so each cell keeps all of them
+            // as its own RustMultiPolygon and the cells still cover the whole extent
+            Some(extent) => clip_cells(&cells, &extent)
+                .into_iter()
+                .map(|cell| shape_to_py(py, Shapes::MultiPolygon(Arc::new(cell)), srid))
+                .collect::<PyResult<Vec<Py<PyAny>>>>()?
+                .into_py_any(py),
+        }
+    }
+
+    #[pyfunction]
+    fn polygonize<'py>(py: Python<'py>, lines: Linework) -> PyResult<Py<PyAny>> {
+        let (polygons, _, _, srid) = linework_polygonize(lines)?;
+        shape_to_py(py, Shapes::MultiPolygon(Arc::new(polygons)), srid)
+    }
+
+    #[pyfunction]
+    fn polygonize_full<'py>(
+        py: Python<'py>,
+        lines: Linework,
+    ) -> PyResult<(Py<PyAny>, Py<PyAny>, Py<PyAny>)> {
+        let (polygons, dangles, cut_edges, srid) = linework_polygonize(lines)?;
+        Ok((
+            shape_to_py(py, Shapes::MultiPolygon(Arc::new(polygons)), srid)?,
+            shape_to_py(py, Shapes::MultiLineString(Arc::new(dangles)), srid)?,
+            shape_to_py(py, Shapes::MultiLineString(Arc::new(cut_edges)), srid)?,
+        ))
+    }
+
+    #[pyfunction]
+    fn bounds_array<'py>(py: Python<'py>, shapes: Vec<Shape>) -> Bound<'py, PyArray2<f64>> {
+        let mut arr = Array2::zeros((shapes.len(), 4));
+        shapes.iter().enumerate().for_each(|(i, shape)| {
+            let (minx, miny, maxx, maxy) = rect_to_bounds(shape_bounding_rect(&shape.inner));
+            arr[[i, 0]] = minx;
+            arr[[i, 1]] = miny;
+            arr[[i, 2]] = maxx;
+            arr[[i, 3]] = maxy;
+        });
+        PyArray2::from_owned_array(py, arr)
+    }
+
     #[pyfunction]
-    fn count<'py>(rust_points: Vec<RustPoint>) -> PyResult<()> {
+    fn count(rust_points: Vec<RustPoint>) -> PyResult<()> {
         println!("Some text {}", rust_points.len());
         Ok(())
     }
 
     #[pyfunction]
-    fn point_in_polygon<'py>(rust_point: RustPoint, rust_polygon: RustPolygon) -> PyResult<bool> {
+    fn point_in_polygon(
+        rust_point: PyRef<'_, RustPoint>,
+        rust_polygon: PyRef<'_, RustPolygon>,
+    ) -> PyResult<bool> {
+        common_srid(rust_point.as_super().srid, rust_polygon.as_super().srid)?;
         let point = rust_point.point.as_ref();
-        let polygon = rust_polygon.polygon;
+        let polygon = &rust_polygon.polygon;
         let is_in = polygon.as_ref().contains(point);
         Ok(is_in)
     }
 
     #[pyfunction(name = "intersection")]
+    #[pyo3(signature = (polygon_lhs, polygon_rhs, grid_size=None))]
     fn intersection<'py>(
         py: Python<'py>,
-        polygon_lhs: &RustPolygon,
-        polygon_rhs: &RustPolygon,
+        polygon_lhs: PyRef<'py, RustPolygon>,
+        polygon_rhs: PyRef<'py, RustPolygon>,
+        grid_size: Option<f64>,
     ) -> PyResult<Py<PyAny>> {
-        let intersection = polygon_lhs
-            .polygon
-            .intersection(polygon_rhs.polygon.as_ref());
+        let srid = common_srid(polygon_lhs.as_super().srid, polygon_rhs.as_super().srid)?;
+        let intersection = match grid_size {
+            Some(grid_size) => {
+                check_grid_size(grid_size)?;
+                let lhs =
+                    set_precision_polygons(&[polygon_lhs.polygon.as_ref().clone()], grid_size);
+                let rhs =
+                    set_precision_polygons(&[polygon_rhs.polygon.as_ref().clone()], grid_size);
+                set_precision_polygons(&lhs.intersection(&rhs).0, grid_size)
+            }
+            None => polygon_lhs
+                .polygon
+                .intersection(polygon_rhs.polygon.as_ref()),
+        };
         let multipolygon_arc = Arc::new(intersection);
         let initializer: PyClassInitializer<RustMultiPolygon> = PyClassInitializer::from((
             RustMultiPolygon {
@@ -1002,8 +4007,440 @@ mod rust_geo_python {
             },
             Shape {
                 inner: Shapes::MultiPolygon(multipolygon_arc),
+                srid,
             },
         ));
         Ok(Py::new(py, initializer)?.into_any())
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn square(x0: f64, y0: f64, size: f64) -> Polygon {
+            Polygon::new(
+                LineString::from(vec![
+                    (x0, y0),
+                    (x0 + size, y0),
+                    (x0 + size, y0 + size),
+                    (x0, y0 + size),
+                    (x0, y0),
+                ]),
+                vec![],
+            )
+        }
+
+        #[test]
+        fn simplify_coverage_keeps_single_point_rings() {
+            let degenerate = Polygon::new(LineString::from(vec![(5.0, 5.0); 4]), vec![]);
+            let polygons = vec![square(0.0, 0.0, 1.0), degenerate.clone()];
+            let neighbours = coverage_neighbours(&polygons);
+            let mut arcs = HashMap::new();
+            let simple = simplify_coverage_polygon(&degenerate, 0.1, &neighbours, &mut arcs);
+            assert_eq!(simple, Some(degenerate));
+        }
+
+        fn buffer_area(shape: Shapes, distance: f64, cap: &str, join: &str, single: bool) -> f64 {
+            let options = BufferOptions::new(cap, join, 5.0, None, single).unwrap();
+            buffer_shape(&shape, distance, &options).unsigned_area()
+        }
+
+        #[test]
+        fn buffer_erodes_polygon_and_grows_hole() {
+            let hole = square(4.0, 4.0, 2.0).exterior().clone();
+            let polygon = Polygon::new(square(0.0, 0.0, 10.0).exterior().clone(), vec![hole]);
+            let options = BufferOptions::new("round", "mitre", 5.0, None, false).unwrap();
+            let eroded = buffer_shape(&Shapes::Polygon(Arc::new(polygon)), -1.0, &options);
+            assert_eq!(eroded.0.len(), 1);
+            assert!((eroded.unsigned_area() - 48.0).abs() < 1e-9);
+            let interiors = eroded.0[0].interiors();
+            assert_eq!(interiors.len(), 1);
+            let hole = Polygon::new(interiors[0].clone(), vec![]);
+            assert!((hole.unsigned_area() - 16.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn buffer_cap_and_join_styles() {
+            let line =
+                || Shapes::LineString(Arc::new(LineString::from(vec![(0.0, 0.0), (10.0, 0.0)])));
+            assert!((buffer_area(line(), 1.0, "flat", "round", false) - 20.0).abs() < 1e-9);
+            assert!((buffer_area(line(), 1.0, "square", "round", false) - 24.0).abs() < 1e-9);
+            let round = buffer_area(line(), 1.0, "round", "round", false);
+            assert!((round - (20.0 + std::f64::consts::PI)).abs() < 0.05);
+
+            let corner = || {
+                Shapes::LineString(Arc::new(LineString::from(vec![
+                    (0.0, 0.0),
+                    (10.0, 0.0),
+                    (10.0, 10.0),
+                ])))
+            };
+            assert!((buffer_area(corner(), 1.0, "flat", "mitre", false) - 40.0).abs() < 1e-9);
+            assert!((buffer_area(corner(), 1.0, "flat", "bevel", false) - 39.5).abs() < 1e-9);
+            let round = buffer_area(corner(), 1.0, "flat", "round", false);
+            assert!((round - (39.0 + std::f64::consts::FRAC_PI_4)).abs() < 0.01);
+        }
+
+        #[test]
+        fn buffer_single_sided() {
+            let corner = || {
+                Shapes::LineString(Arc::new(LineString::from(vec![
+                    (0.0, 0.0),
+                    (10.0, 0.0),
+                    (10.0, 10.0),
+                ])))
+            };
+            // The corner turns left, so only the right hand side needs a join
+            assert!((buffer_area(corner(), 1.0, "flat", "mitre", true) - 19.0).abs() < 1e-9);
+            assert!((buffer_area(corner(), -1.0, "flat", "mitre", true) - 21.0).abs() < 1e-9);
+            assert!((buffer_area(corner(), -1.0, "flat", "bevel", true) - 20.5).abs() < 1e-9);
+            let round = buffer_area(corner(), -1.0, "flat", "round", true);
+            assert!((round - (20.0 + std::f64::consts::FRAC_PI_4)).abs() < 0.01);
+        }
+
+        #[test]
+        fn to_crs_follows_the_shape_srid() {
+            Python::initialize();
+            Python::attach(|py| {
+                let shape = Shape {
+                    inner: Shapes::Point(Arc::new(Point::new(10.0, 50.0))),
+                    srid: Some(4326),
+                };
+                let srid_of =
+                    |shape: Py<PyAny>| shape.bind(py).cast::<Shape>().unwrap().borrow().srid;
+
+                let projected = shape.to_crs(py, None, Some(Crs::Epsg(3857)), None).unwrap();
+                assert_eq!(srid_of(projected), Some(3857));
+                let projected = shape
+                    .to_crs(py, Some(Crs::Epsg(4326)), Some(Crs::Epsg(3857)), None)
+                    .unwrap();
+                assert_eq!(srid_of(projected), Some(3857));
+                let utm = "+proj=utm +zone=32 +datum=WGS84 +units=m +no_defs".to_string();
+                let projected = shape.to_crs(py, None, Some(Crs::Name(utm)), None).unwrap();
+                assert_eq!(srid_of(projected), Some(32632));
+
+                let custom = "+proj=merc +lon_0=10 +datum=WGS84 +units=m".to_string();
+                assert!(
+                    shape
+                        .to_crs(py, None, Some(Crs::Name(custom.clone())), None)
+                        .is_err()
+                );
+                let projected = shape
+                    .to_crs(py, None, Some(Crs::Name(custom)), Some(900913))
+                    .unwrap();
+                assert_eq!(srid_of(projected), Some(900913));
+                assert!(
+                    shape
+                        .to_crs(py, Some(Crs::Epsg(3857)), Some(Crs::Epsg(4326)), None)
+                        .is_err()
+                );
+            });
+        }
+
+        #[test]
+        fn metric_distance_uses_the_metric_closest_point() {
+            // Meridians are geodesics, so the exact distance is the smallest over their latitudes
+            let meridian =
+                Shapes::LineString(Arc::new(LineString::from(vec![(0.0, 0.0), (0.0, 89.0)])));
+            let point = Shapes::Point(Arc::new(Point::new(1.0, 45.0)));
+            let brute_force = (0..=20_000)
+                .map(|i| {
+                    Geodesic.distance(
+                        Point::new(1.0, 45.0),
+                        Point::new(0.0, 44.9 + i as f64 * 1e-5),
+                    )
+                })
+                .fold(f64::INFINITY, f64::min);
+            let distance = metric_distance(&point, &meridian, &Geodesic);
+            assert!((distance - brute_force).abs() < 1e-3);
+            assert_eq!(metric_distance(&meridian, &meridian, &Rhumb), 0.0);
+
+            // Pruning by the haversine bound must not skip the closest segment
+            let zigzag = (0..60)
+                .map(|i| (i as f64, if i % 2 == 0 { 0.0 } else { 1.5 }))
+                .collect::<LineString>();
+            let segments = measured_segments(zigzag.lines(), &Rhumb);
+            for point in [
+                Point::new(10.3, 3.0),
+                Point::new(-2.0, -1.0),
+                Point::new(33.7, 0.8),
+            ] {
+                let exhaustive = zigzag
+                    .lines()
+                    .map(|l| segment_metric_distance(point, l, &Rhumb))
+                    .fold(f64::INFINITY, f64::min);
+                assert_eq!(point_metric_distance(point, &segments, &Rhumb), exhaustive);
+            }
+        }
+
+        #[test]
+        fn interpolate_measures_negative_fractions_from_the_end() {
+            // interpolate_many maps every fraction through line_interpolate with normalized=true
+            let linestring = LineString::from(vec![(0.0, 0.0), (10.0, 0.0)]);
+            let points = [0.25, -0.25, -1.0]
+                .map(|fraction| line_interpolate(&linestring, fraction, true).unwrap());
+            assert_eq!(
+                points,
+                [
+                    Point::new(2.5, 0.0),
+                    Point::new(7.5, 0.0),
+                    Point::new(0.0, 0.0)
+                ]
+            );
+            assert_eq!(
+                line_interpolate(&linestring, -2.5, false),
+                Some(Point::new(7.5, 0.0))
+            );
+        }
+
+        #[test]
+        fn make_valid_splits_off_collapsed_spikes() {
+            let spiked = Polygon::new(
+                LineString::from(vec![
+                    (0.0, 0.0),
+                    (4.0, 0.0),
+                    (4.0, 4.0),
+                    (2.0, 4.0),
+                    (2.0, 7.0),
+                    (2.0, 4.0),
+                    (0.0, 4.0),
+                    (0.0, 0.0),
+                ]),
+                vec![],
+            );
+            let repaired = make_valid_polygons(std::slice::from_ref(&spiked));
+            assert!((repaired.unsigned_area() - 16.0).abs() < 1e-9);
+            let collapsed = collapsed_linework(&[spiked], &repaired);
+            assert_eq!(
+                collapsed,
+                MultiLineString::new(vec![LineString::from(vec![
+                    (2.0, 4.0),
+                    (2.0, 7.0),
+                    (2.0, 4.0)
+                ])])
+            );
+        }
+
+        #[test]
+        fn dedup_points_matches_pairwise_scan() {
+            // A small linear congruential generator keeps the points deterministic
+            let mut state = 12345u64;
+            let mut next = || {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            };
+            let points = (0..500)
+                .map(|_| Point::new(next() * 2.0 - 1.0, next() * 2.0 - 1.0))
+                .collect::<Vec<Point>>();
+            let tolerance = 0.07;
+            let mut expected: Vec<Point> = vec![];
+            for point in &points {
+                if expected
+                    .iter()
+                    .all(|x| Euclidean.distance(x, point) > tolerance)
+                {
+                    expected.push(*point);
+                }
+            }
+            assert_eq!(dedup_points(&points, tolerance), expected);
+            assert_eq!(dedup_points(&points, 0.0), points);
+        }
+
+        #[test]
+        fn constrained_triangles_keep_small_polygons() {
+            let notched = Polygon::new(
+                LineString::from(vec![
+                    (0.0, 0.0),
+                    (4.0, 0.0),
+                    (4.0, 4.0),
+                    (2.5, 4.0),
+                    (2.0, 1.0),
+                    (1.5, 4.0),
+                    (0.0, 4.0),
+                    (0.0, 0.0),
+                ]),
+                vec![],
+            );
+            for scale in [1.0, 1e-5] {
+                let polygon = notched.map_coords(|c| c * scale);
+                let triangles = constrained_triangles(&polygon, None).unwrap();
+                let area = triangles.iter().map(|t| t.unsigned_area()).sum::<f64>();
+                assert!((area - polygon.unsigned_area()).abs() < 1e-9 * scale * scale);
+            }
+            assert!(constrained_triangles(&notched, Some(-1.0)).is_err());
+        }
+
+        #[test]
+        fn node_lines_splits_crossings() {
+            // A hash sign: each stroke is crossed twice and splits into three edges
+            let lines = [
+                LineString::from(vec![(0.0, 1.0), (3.0, 1.0)]),
+                LineString::from(vec![(0.0, 2.0), (3.0, 2.0)]),
+                LineString::from(vec![(1.0, 0.0), (1.0, 3.0)]),
+                LineString::from(vec![(2.0, 0.0), (2.0, 3.0)]),
+            ];
+            let edges = node_lines(&lines.iter().collect::<Vec<&LineString>>());
+            assert_eq!(edges.len(), 12);
+            assert!(edges.contains(&(Coord { x: 1.0, y: 1.0 }, Coord { x: 2.0, y: 1.0 })));
+            assert!(edges.contains(&(Coord { x: 2.0, y: 2.0 }, Coord { x: 2.0, y: 3.0 })));
+        }
+
+        #[test]
+        fn curve_distances_need_linestrings() {
+            let line = |ys: [f64; 3]| Shape {
+                inner: Shapes::LineString(Arc::new(LineString::from(vec![
+                    (0.0, ys[0]),
+                    (1.0, ys[1]),
+                    (2.0, ys[2]),
+                ]))),
+                srid: None,
+            };
+            let (a, b) = (line([0.0, 0.0, 0.0]), line([1.0, 1.0, 1.0]));
+            assert_eq!(a.frechet_distance(&b, "euclidean").unwrap(), 1.0);
+            assert_eq!(a.dtw_distance(&b, "euclidean").unwrap(), 3.0);
+            let point = Shape {
+                inner: Shapes::Point(Arc::new(Point::new(0.0, 0.0))),
+                srid: None,
+            };
+            assert!(a.frechet_distance(&point, "euclidean").is_err());
+            assert!(point.dtw_distance(&a, "euclidean").is_err());
+        }
+
+        #[test]
+        fn smooth_caps_iterations() {
+            Python::initialize();
+            Python::attach(|py| {
+                let line = Shape {
+                    inner: Shapes::LineString(Arc::new(LineString::from(vec![
+                        (0.0, 0.0),
+                        (1.0, 1.0),
+                        (2.0, 0.0),
+                    ]))),
+                    srid: None,
+                };
+                let smoothed = line.smooth(py, 2).unwrap();
+                let smoothed = smoothed.bind(py).cast::<Shape>().unwrap().borrow();
+                assert!(matches!(&smoothed.inner, Shapes::LineString(p) if p.0.len() > 6));
+                assert!(line.smooth(py, 11).is_err());
+            });
+        }
+
+        #[test]
+        fn clipped_voronoi_cells_cover_the_extent() {
+            // The lower site's cell reaches both squares, so it must keep both pieces
+            let extent = MultiPolygon::new(vec![square(0.0, 0.0, 1.0), square(3.0, 0.0, 1.0)]);
+            let sites = [Coord { x: 2.0, y: 0.5 }, Coord { x: 2.0, y: 5.0 }];
+            let frame = Rect::new(Coord { x: -10.0, y: -10.0 }, Coord { x: 10.0, y: 10.0 });
+            let cells = clip_cells(&voronoi_cells(&sites, frame).unwrap(), &extent);
+            assert_eq!(cells.len(), 2);
+            assert_eq!(cells[0].0.len(), 2);
+            let area = cells.iter().map(|c| c.unsigned_area()).sum::<f64>();
+            assert!((area - extent.unsigned_area()).abs() < 1e-9);
+        }
+
+        #[test]
+        fn earcut_buffers_skip_closing_vertices() {
+            let hole = square(1.0, 1.0, 1.0).exterior().clone();
+            let polygons = [
+                Polygon::new(square(0.0, 0.0, 3.0).exterior().clone(), vec![hole]),
+                square(5.0, 0.0, 1.0),
+            ];
+            let (vertices, indices) = earcut_buffers(&polygons).unwrap();
+            assert_eq!(vertices.len(), 2 * 12);
+            assert_eq!(indices.len(), 3 * (8 + 2));
+            let mut used = indices.iter().map(|&i| i as usize).collect::<Vec<usize>>();
+            used.sort_unstable();
+            used.dedup();
+            assert_eq!(used, (0..12).collect::<Vec<usize>>());
+            let area = indices
+                .chunks(3)
+                .map(|t| {
+                    let c = |i: u32| Coord {
+                        x: vertices[2 * i as usize],
+                        y: vertices[2 * i as usize + 1],
+                    };
+                    Triangle::new(c(t[0]), c(t[1]), c(t[2])).unsigned_area()
+                })
+                .sum::<f64>();
+            assert!((area - 9.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn node_lines_snaps_shared_crossings() {
+            // Three strokes through one irrational point, each pair intersects a few ulps apart
+            let center = Coord {
+                x: 2.0_f64.sqrt(),
+                y: 3.0_f64.sqrt(),
+            };
+            let lines = [0.3_f64, 1.1, 2.5]
+                .iter()
+                .map(|angle| {
+                    let d = Coord {
+                        x: angle.cos() * 7.0,
+                        y: angle.sin() * 7.0,
+                    };
+                    LineString::from(vec![center - d, center + d])
+                })
+                .collect::<Vec<LineString>>();
+            let edges = node_lines(&lines.iter().collect::<Vec<&LineString>>());
+            assert_eq!(edges.len(), 6);
+            let nodes = edges
+                .iter()
+                .flat_map(|(a, b)| [coord_key(*a), coord_key(*b)])
+                .collect::<HashSet<CoordKey>>();
+            assert_eq!(nodes.len(), 7);
+        }
+
+        #[test]
+        fn polygonize_lines_splits_off_dangles() {
+            let spur = LineString::from(vec![(1.0, 1.0), (3.0, 3.0)]);
+            let lines = [square(0.0, 0.0, 1.0).exterior().clone(), spur];
+            let (polygons, dangles, cut_edges) =
+                polygonize_lines(&lines.iter().collect::<Vec<&LineString>>());
+            assert_eq!(polygons.0.len(), 1);
+            assert!((polygons.unsigned_area() - 1.0).abs() < 1e-9);
+            assert_eq!(dangles.0.len(), 1);
+            assert!(cut_edges.0.is_empty());
+        }
+
+        #[test]
+        fn polygonize_lines_finds_cut_edges() {
+            let bridge = LineString::from(vec![(1.0, 0.5), (2.0, 0.5)]);
+            let lines = [
+                square(0.0, 0.0, 1.0).exterior().clone(),
+                square(2.0, 0.0, 1.0).exterior().clone(),
+                bridge,
+            ];
+            let (polygons, dangles, cut_edges) =
+                polygonize_lines(&lines.iter().collect::<Vec<&LineString>>());
+            assert_eq!(polygons.0.len(), 2);
+            assert!(dangles.0.is_empty());
+            assert_eq!(cut_edges.0.len(), 1);
+            assert!((Euclidean.length(&cut_edges.0[0]) - 1.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn polygonize_lines_nests_holes() {
+            let lines = [
+                square(0.0, 0.0, 3.0).exterior().clone(),
+                square(1.0, 1.0, 1.0).exterior().clone(),
+            ];
+            let (polygons, dangles, cut_edges) =
+                polygonize_lines(&lines.iter().collect::<Vec<&LineString>>());
+            assert!(dangles.0.is_empty() && cut_edges.0.is_empty());
+            let mut areas = polygons
+                .0
+                .iter()
+                .map(|p| (p.interiors().len(), p.unsigned_area()))
+                .collect::<Vec<(usize, f64)>>();
+            areas.sort_by(|a, b| a.1.total_cmp(&b.1));
+            assert_eq!(areas.len(), 2);
+            assert_eq!(areas[0].0, 0);
+            assert!((areas[0].1 - 1.0).abs() < 1e-9);
+            assert_eq!(areas[1].0, 1);
+            assert!((areas[1].1 - 8.0).abs() < 1e-9);
+        }
+    }
 }